@@ -0,0 +1,281 @@
+//! ---------------------------------------------------------------------------
+//! Bench de **asignaciones por frame** de las consultas de alta frecuencia.
+//!
+//! Cuenta las asignaciones del asignador global mientras se ejecuta el ciclo
+//! de frame típico sobre los backends de referencia:
+//!
+//! ```text
+//! poll_events_into → RuntimeInputExt (filter_into → procesamiento → acciones)
+//!     → pressed_keys_iter / key_states_iter → drain_action_events_into
+//! ```
+//!
+//! El runtime es el `TestRuntime` del módulo oculto `testing` (el mismo de
+//! los tests del crate), con el [`StandardInputFilter`] configurado con
+//! modificadores persistentes.
+//!
+//! Tras unos frames de calentamiento, las variantes sin asignación no deben
+//! asignar nada (el nombre de cada acción es un `Arc<str>` que se clona); el
+//! bench falla si lo hacen. Las variantes que devuelven `Vec` se miden como
+//! comparación.
+//!
+//! ```text
+//! cargo bench --bench allocations --features "scripted evdev terminal testing"
+//! ```
+//! ---------------------------------------------------------------------------
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    io::{self, Read},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use igni_input_core::{
+    backends::{
+        evdev::{EV_KEY, EV_REL, EV_SYN, EvdevDecoder, EvdevRawLayer, EventLayout, SYN_REPORT},
+        scripted::ScriptedRawLayer,
+        terminal::TerminalRawLayer,
+    },
+    contracts::{game_contract::GameContract, runtime_contract::RuntimeInputExt},
+    layers::{
+        filters::{AccessibilityFilters, StickyModifiers},
+        processing_layer::ProcessingLayerState,
+        raw_layer::RawInputLayer,
+        standard_keys::{StandardKey, StandardKeyEvent, StandardKeyState},
+        timestamp::Timestamp,
+    },
+    testing::{TestMapping, TestRuntime},
+};
+
+
+/// Frames de calentamiento (los buffers alcanzan su tamaño de trabajo).
+const WARMUP_FRAMES: usize = 64;
+/// Frames medidos.
+const MEASURED_FRAMES: usize = 4096;
+
+
+// -----------------------------------------------------------------------------
+// Asignador contador
+// -----------------------------------------------------------------------------
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+
+// -----------------------------------------------------------------------------
+// Runtime
+// -----------------------------------------------------------------------------
+
+/// Acciones del bench: nombre → tecla.
+const ACTIONS: [(&str, &[StandardKey]); 3] =
+    [("Jump", &[StandardKey::Space]), ("Interact", &[StandardKey::A]), ("MenuUp", &[StandardKey::ArrowUp])];
+
+/// Runtime de test del crate con una acción por tecla de [`ACTIONS`] y
+/// modificadores persistentes.
+fn runtime() -> TestRuntime {
+    // Teclas lentas y de rebote descartarían las pulsaciones de los
+    // escenarios, que repiten timestamps entre frames.
+    let config = AccessibilityFilters { sticky_modifiers: Some(StickyModifiers::default()), ..Default::default() };
+    TestRuntime::new(TestMapping::default().with_context("game", &ACTIONS)).with_filters(config)
+}
+
+
+// -----------------------------------------------------------------------------
+// Escenarios
+// -----------------------------------------------------------------------------
+
+/// Lector no bloqueante que entrega el mismo trozo una vez por frame.
+struct FrameReader {
+    chunk: Vec<u8>,
+    served: bool,
+}
+
+impl Read for FrameReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.served {
+            self.served = false;
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.served = true;
+        let len = self.chunk.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.chunk[..len]);
+        Ok(len)
+    }
+}
+
+fn evdev_record(bytes: &mut Vec<u8>, usec: i64, kind: u16, code: u16, value: i32) {
+    bytes.extend_from_slice(&0i64.to_ne_bytes());
+    bytes.extend_from_slice(&usec.to_ne_bytes());
+    bytes.extend_from_slice(&kind.to_ne_bytes());
+    bytes.extend_from_slice(&code.to_ne_bytes());
+    bytes.extend_from_slice(&value.to_ne_bytes());
+}
+
+/// Un frame de evdev: `A` presionada y liberada, movimiento del mouse.
+fn evdev_layer() -> EvdevRawLayer<FrameReader> {
+    const KEY_A: u16 = 30;
+    const REL_X: u16 = 0;
+    const REL_Y: u16 = 1;
+
+    let mut chunk = Vec::new();
+    evdev_record(&mut chunk, 0, EV_KEY, KEY_A, 1);
+    evdev_record(&mut chunk, 0, EV_SYN, SYN_REPORT, 0);
+    evdev_record(&mut chunk, 4_000, EV_REL, REL_X, 3);
+    evdev_record(&mut chunk, 4_000, EV_REL, REL_Y, -2);
+    evdev_record(&mut chunk, 4_000, EV_SYN, SYN_REPORT, 0);
+    evdev_record(&mut chunk, 8_000, EV_KEY, KEY_A, 0);
+    evdev_record(&mut chunk, 8_000, EV_SYN, SYN_REPORT, 0);
+
+    let decoder = EvdevDecoder::new().with_layout(EventLayout::Time64).with_base_timestamp(Timestamp::ZERO);
+    EvdevRawLayer::with_decoder(FrameReader { chunk, served: false }, decoder)
+}
+
+/// Un frame de terminal: flechas, Shift+Tab y un clic SGR con arrastre.
+fn terminal_layer() -> TerminalRawLayer<FrameReader> {
+    let chunk = b"\x1b[A\x1b[1;5B\x1b[Z\x1b[<0;10;5M\x1b[<32;12;6M\x1b[<0;12;6m".to_vec();
+    TerminalRawLayer::new(FrameReader { chunk, served: false })
+}
+
+/// Un script con actividad en todos los frames.
+fn scripted_layer() -> ScriptedRawLayer {
+    let frames = WARMUP_FRAMES + MEASURED_FRAMES;
+    let script: String = (0..frames)
+        .map(|frame| match frame % 3 {
+            0 => format!("frame {frame}: press Space; tap ArrowLeft\n"),
+            1 => format!("frame {frame}: repeat Space\n"),
+            _ => format!("frame {frame}: release Space; tap W\n"),
+        })
+        .collect();
+    ScriptedRawLayer::parse(&script).expect("script válido")
+}
+
+
+// -----------------------------------------------------------------------------
+// Medición
+// -----------------------------------------------------------------------------
+
+struct Measurement {
+    allocations: usize,
+    actions: usize,
+    nanos_per_frame: f64,
+}
+
+impl Measurement {
+    fn per_frame(count: usize) -> f64 {
+        count as f64 / MEASURED_FRAMES as f64
+    }
+}
+
+/// Ejecuta `frame` durante el calentamiento y mide los frames siguientes.
+///
+/// `frame` devuelve el número de acciones drenadas.
+fn measure(mut frame: impl FnMut() -> usize) -> Measurement {
+    for _ in 0..WARMUP_FRAMES {
+        frame();
+    }
+    let before = allocations();
+    let start = Instant::now();
+    let mut actions = 0;
+    for _ in 0..MEASURED_FRAMES {
+        actions += frame();
+    }
+    let elapsed = start.elapsed();
+    Measurement {
+        allocations: allocations() - before,
+        actions,
+        nanos_per_frame: elapsed.as_nanos() as f64 / MEASURED_FRAMES as f64,
+    }
+}
+
+/// Ciclo de frame sin asignaciones: buffers reutilizados e iteradores.
+fn run_into<L: RawInputLayer<KeyEvent = StandardKeyEvent>>(mut layer: L) -> Measurement {
+    let mut runtime = runtime();
+    let mut raw_events = Vec::new();
+    let mut actions = Vec::new();
+    measure(|| {
+        runtime.begin_frame();
+        raw_events.clear();
+        layer.poll_events_into(&mut raw_events);
+        for event in raw_events.drain(..) {
+            runtime.push_raw_event(event);
+        }
+        runtime.end_frame();
+        let processing = runtime.game_layer().processing_layer();
+        black_box(processing.pressed_keys_iter().count());
+        black_box(processing.key_states_iter().filter(|(_, state)| *state == StandardKeyState::Changed).count());
+        actions.clear();
+        runtime.drain_action_events_into(&mut actions);
+        black_box(&actions).len()
+    })
+}
+
+/// Ciclo de frame con las variantes que devuelven `Vec`.
+fn run_vec<L: RawInputLayer<KeyEvent = StandardKeyEvent>>(mut layer: L) -> Measurement {
+    let mut runtime = runtime();
+    measure(|| {
+        runtime.begin_frame();
+        for event in layer.poll_events() {
+            runtime.push_raw_event(event);
+        }
+        runtime.end_frame();
+        let processing = runtime.game_layer().processing_layer();
+        black_box(processing.all_pressed_keys());
+        black_box(processing.current_state_snapshot());
+        black_box(runtime.drain_action_events()).len()
+    })
+}
+
+fn main() {
+    // Fija el origen del reloj del proceso antes de medir.
+    black_box(Timestamp::now());
+
+    let scenarios: [(&str, Measurement, Measurement); 3] = [
+        ("scripted", run_into(scripted_layer()), run_vec(scripted_layer())),
+        ("evdev", run_into(evdev_layer()), run_vec(evdev_layer())),
+        ("terminal", run_into(terminal_layer()), run_vec(terminal_layer())),
+    ];
+
+    println!(
+        "{:<10} {:>9} {:>26} {:>22}",
+        "backend", "actions", "into + iter (allocs/ns)", "Vec (allocs/ns)"
+    );
+    let mut failed = false;
+    for (name, into, vec) in &scenarios {
+        println!(
+            "{:<10} {:>9.2} {:>16.2} / {:>7.0} {:>12.2} / {:>7.0}",
+            name,
+            Measurement::per_frame(into.actions),
+            Measurement::per_frame(into.allocations),
+            into.nanos_per_frame,
+            Measurement::per_frame(vec.allocations),
+            vec.nanos_per_frame,
+        );
+        failed |= into.allocations != 0;
+    }
+    assert!(!failed, "las variantes sin asignación asignaron memoria tras el calentamiento");
+}
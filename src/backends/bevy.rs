@@ -0,0 +1,727 @@
+//! ---------------------------------------------------------------------------
+//! Integración con **Bevy** (0.18).
+//!
+//! [`IgniInputPlugin`] ejecuta el ciclo de frame de un
+//! [`RuntimeInputExt`] dentro de `PreUpdate`, después de `InputSystems`:
+//!
+//! ```text
+//! IgniInputSystems::BeginFrame → IgniInputSystems::Forward → IgniInputSystems::EndFrame
+//! ```
+//!
+//! - `BeginFrame` llama a `begin_frame`.
+//! - `Forward` reenvía los mensajes de teclado, mouse y gamepad de Bevy como
+//!   [`StandardKeyEvent`], el texto de las teclas como [`TextInputEvent`] y
+//!   las conexiones de mandos como [`DeviceChange`] y el foco de las ventanas
+//!   como [`RawSignal::FocusLost`] / [`RawSignal::FocusGained`]. Los sistemas propios
+//!   que inyecten entrada deben ir en este set.
+//! - `EndFrame` llama a `end_frame` y publica la cola de acciones como
+//!   mensajes [`IgniActionMessage`].
+//!
+//! El runtime vive en el recurso [`IgniInput`], que expone el
+//! [`GameContract`] para los sistemas de `Update`:
+//!
+//! ```ignore
+//! App::new()
+//!     .add_plugins(IgniInputPlugin::new(my_runtime))
+//!     .add_systems(Update, |input: Res<IgniInput<MyRuntime>>| {
+//!         if input.game().action_just_pressed("Jump") { /* ... */ }
+//!     });
+//! ```
+//!
+//! El plugin registra los mensajes que lee, de modo que funciona en una
+//! `App` sin ventana ni `InputPlugin` (tests que escriben los mensajes a mano).
+//! ---------------------------------------------------------------------------
+
+use std::sync::Mutex;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    entity::Entity,
+    message::{Message, MessageReader, MessageWriter},
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::{Local, ResMut},
+};
+use bevy_input::{
+    ButtonState, InputSystems,
+    gamepad::{GamepadAxis, GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent},
+    keyboard::{KeyCode, KeyboardFocusLost, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+};
+use bevy_window::WindowFocused;
+
+use crate::{
+    contracts::{game_contract::GameContract, runtime_contract::RuntimeInputExt, subscription_contract::DeviceChange},
+    layers::{
+        action_events::ActionEvent,
+        mapping_layer::MappingLayerState,
+        raw_layer::{KeyEventExt, RawSignal, TextInputEvent},
+        standard_keys::{StandardDevice, StandardKey, StandardKeyEvent, StandardKeyState},
+        timestamp::Timestamp,
+    },
+};
+
+
+// -----------------------------------------------------------------------------
+// Tablas
+// -----------------------------------------------------------------------------
+
+/// Genera la tabla `KeyCode` → `StandardKey` para variantes homónimas.
+macro_rules! same_name_codes {
+    ($code:expr; $( $bevy:ident => $standard:ident ),* ; $( $name:ident ),* $(,)?) => {
+        match $code {
+            $( KeyCode::$bevy => Some(StandardKey::$standard), )*
+            $( KeyCode::$name => Some(StandardKey::$name), )*
+            _ => None,
+        }
+    };
+}
+
+/// Tecla estándar de un `KeyCode` de Bevy.
+pub fn standard_key_for_code(code: KeyCode) -> Option<StandardKey> {
+    same_name_codes!(code;
+        KeyA => A, KeyB => B, KeyC => C, KeyD => D, KeyE => E, KeyF => F, KeyG => G,
+        KeyH => H, KeyI => I, KeyJ => J, KeyK => K, KeyL => L, KeyM => M, KeyN => N,
+        KeyO => O, KeyP => P, KeyQ => Q, KeyR => R, KeyS => S, KeyT => T, KeyU => U,
+        KeyV => V, KeyW => W, KeyX => X, KeyY => Y, KeyZ => Z;
+        Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+        Escape, Tab, CapsLock, Space, Enter, Backspace,
+        ShiftLeft, ShiftRight, ControlLeft, ControlRight, AltLeft, AltRight,
+        SuperLeft, SuperRight, ContextMenu,
+        ArrowUp, ArrowDown, ArrowLeft, ArrowRight,
+        Insert, Delete, Home, End, PageUp, PageDown,
+        PrintScreen, ScrollLock, Pause,
+        Backquote, Minus, Equal, BracketLeft, BracketRight, Backslash,
+        Semicolon, Quote, Comma, Period, Slash,
+        NumLock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6,
+        Numpad7, Numpad8, Numpad9, NumpadAdd, NumpadSubtract, NumpadMultiply,
+        NumpadDivide, NumpadDecimal, NumpadEnter,
+    )
+}
+
+/// Botón estándar de un botón de mouse de Bevy.
+pub fn standard_mouse_button(button: MouseButton) -> Option<StandardKey> {
+    match button {
+        MouseButton::Left => Some(StandardKey::MouseLeft),
+        MouseButton::Right => Some(StandardKey::MouseRight),
+        MouseButton::Middle => Some(StandardKey::MouseMiddle),
+        MouseButton::Back => Some(StandardKey::MouseBack),
+        MouseButton::Forward => Some(StandardKey::MouseForward),
+        MouseButton::Other(_) => None,
+    }
+}
+
+/// Botón estándar de un botón de gamepad de Bevy.
+///
+/// `LeftTrigger` / `RightTrigger` de Bevy son los bumpers (L1 / R1);
+/// `LeftTrigger2` / `RightTrigger2`, los gatillos (L2 / R2).
+pub fn standard_gamepad_button(button: GamepadButton) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match button {
+        GamepadButton::South => GamepadSouth,
+        GamepadButton::East => GamepadEast,
+        GamepadButton::North => GamepadNorth,
+        GamepadButton::West => GamepadWest,
+        GamepadButton::LeftTrigger => GamepadLeftBumper,
+        GamepadButton::RightTrigger => GamepadRightBumper,
+        GamepadButton::LeftTrigger2 => GamepadLeftTrigger,
+        GamepadButton::RightTrigger2 => GamepadRightTrigger,
+        GamepadButton::Select => GamepadSelect,
+        GamepadButton::Start => GamepadStart,
+        GamepadButton::Mode => GamepadMode,
+        GamepadButton::LeftThumb => GamepadLeftStick,
+        GamepadButton::RightThumb => GamepadRightStick,
+        GamepadButton::DPadUp => GamepadDPadUp,
+        GamepadButton::DPadDown => GamepadDPadDown,
+        GamepadButton::DPadLeft => GamepadDPadLeft,
+        GamepadButton::DPadRight => GamepadDPadRight,
+        GamepadButton::C | GamepadButton::Z | GamepadButton::Other(_) => return None,
+    };
+    Some(key)
+}
+
+/// Eje estándar de un eje de gamepad de Bevy.
+pub fn standard_gamepad_axis(axis: GamepadAxis) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match axis {
+        GamepadAxis::LeftStickX => GamepadLeftStickX,
+        GamepadAxis::LeftStickY => GamepadLeftStickY,
+        GamepadAxis::RightStickX => GamepadRightStickX,
+        GamepadAxis::RightStickY => GamepadRightStickY,
+        GamepadAxis::LeftZ => GamepadLeftTriggerAxis,
+        GamepadAxis::RightZ => GamepadRightTriggerAxis,
+        GamepadAxis::Other(_) => return None,
+    };
+    Some(key)
+}
+
+/// Eje analógico asociado a un gatillo informado como botón.
+fn trigger_axis(button: GamepadButton) -> Option<StandardKey> {
+    match button {
+        GamepadButton::LeftTrigger2 => Some(StandardKey::GamepadLeftTriggerAxis),
+        GamepadButton::RightTrigger2 => Some(StandardKey::GamepadRightTriggerAxis),
+        _ => None,
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Recurso, mensajes y sets
+// -----------------------------------------------------------------------------
+
+/// Tipo de dispositivo de los eventos de acción de un runtime.
+pub type ActionDevice<R> =
+    <<<R as RuntimeInputExt>::GameLayer as GameContract>::MappingLayer as MappingLayerState>::DeviceKind;
+
+/// Runtime utilizable desde Bevy.
+///
+/// Sus eventos crudos deben poder construirse desde [`StandardKeyEvent`] y
+/// sus dispositivos desde [`StandardDevice`]. Se implementa automáticamente.
+pub trait BevyInputRuntime:
+    RuntimeInputExt<
+        Event: From<StandardKeyEvent> + KeyEventExt<DeviceKind: From<StandardDevice>>,
+        GameLayer: GameContract<MappingLayer: MappingLayerState<DeviceKind: Send + Sync + 'static>>,
+    > + Send
+    + Sync
+    + 'static
+{
+}
+
+impl<R> BevyInputRuntime for R where
+    R: RuntimeInputExt<
+            Event: From<StandardKeyEvent> + KeyEventExt<DeviceKind: From<StandardDevice>>,
+            GameLayer: GameContract<MappingLayer: MappingLayerState<DeviceKind: Send + Sync + 'static>>,
+        > + Send
+        + Sync
+        + 'static
+{
+}
+
+/// Recurso con el runtime de entrada y el estado del reenvío de mandos.
+pub struct IgniInput<R> {
+    runtime: R,
+    /// Entidad de Bevy ocupando cada índice estable de gamepad.
+    pads: Vec<Option<Entity>>,
+    /// Botones de gamepad presionados, por índice estable.
+    held_pad_buttons: Vec<(u32, StandardKey)>,
+    /// Ejes de gamepad fuera de reposo, por índice estable.
+    active_pad_axes: Vec<(u32, StandardKey)>,
+    /// Ventanas con foco según los `WindowFocused` recibidos.
+    focused_windows: Vec<Entity>,
+    /// Último estado de foco comunicado al runtime (al crearse se asume foco).
+    focused: bool,
+}
+
+impl<R: Send + Sync + 'static> Resource for IgniInput<R> {}
+
+impl<R: RuntimeInputExt> IgniInput<R> {
+    /// Envuelve un runtime.
+    pub fn new(runtime: R) -> Self {
+        Self {
+            runtime,
+            pads: Vec::new(),
+            held_pad_buttons: Vec::new(),
+            active_pad_axes: Vec::new(),
+            focused_windows: Vec::new(),
+            focused: true,
+        }
+    }
+
+    /// Capa de juego (solo lectura).
+    pub fn game(&self) -> &R::GameLayer {
+        self.runtime.game_layer()
+    }
+
+    /// Runtime de entrada.
+    pub fn runtime(&self) -> &R {
+        &self.runtime
+    }
+
+    /// Runtime de entrada, mutable (inyección, configuración de capas...).
+    pub fn runtime_mut(&mut self) -> &mut R {
+        &mut self.runtime
+    }
+
+    /// Índice estable asignado a la entidad de un gamepad, si está conectado.
+    pub fn pad_id(&self, gamepad: Entity) -> Option<u32> {
+        self.pads.iter().position(|slot| *slot == Some(gamepad)).map(|index| index as u32)
+    }
+
+    /// Devuelve el runtime, descartando el estado del reenvío.
+    pub fn into_inner(self) -> R {
+        self.runtime
+    }
+}
+
+impl<R: BevyInputRuntime> IgniInput<R> {
+    fn push(&mut self, key: StandardKey, state: StandardKeyState, device: StandardDevice, repeat: bool, value: Option<f32>) {
+        let event = StandardKeyEvent { key, state, timestamp: Timestamp::now(), device, repeat, value };
+        self.runtime.push_raw_event(event.into());
+    }
+
+    /// Devuelve el índice estable del gamepad, asignando el menor libre si es nuevo.
+    fn connect_pad(&mut self, gamepad: Entity) -> u32 {
+        if let Some(pad) = self.pad_id(gamepad) {
+            return pad;
+        }
+        let index = match self.pads.iter().position(Option::is_none) {
+            Some(index) => index,
+            None => {
+                self.pads.push(None);
+                self.pads.len() - 1
+            }
+        };
+        self.pads[index] = Some(gamepad);
+        let pad = index as u32;
+        self.runtime.push_device_change(StandardDevice::Gamepad(pad).into(), DeviceChange::Connected);
+        pad
+    }
+
+    /// Libera botones y ejes del gamepad y su índice estable.
+    fn disconnect_pad(&mut self, gamepad: Entity) {
+        let Some(pad) = self.pad_id(gamepad) else { return };
+        let device = StandardDevice::Gamepad(pad);
+
+        let held: Vec<StandardKey> =
+            self.held_pad_buttons.iter().filter(|(p, _)| *p == pad).map(|(_, key)| *key).collect();
+        for key in held {
+            self.push(key, StandardKeyState::Released, device, false, None);
+        }
+        let axes: Vec<StandardKey> =
+            self.active_pad_axes.iter().filter(|(p, _)| *p == pad).map(|(_, key)| *key).collect();
+        for axis in axes {
+            self.push(axis, StandardKeyState::Changed, device, false, Some(0.0));
+        }
+
+        self.held_pad_buttons.retain(|(p, _)| *p != pad);
+        self.active_pad_axes.retain(|(p, _)| *p != pad);
+        self.pads[pad as usize] = None;
+        self.runtime.push_device_change(device.into(), DeviceChange::Disconnected);
+    }
+
+    fn set_pad_axis(&mut self, pad: u32, axis: StandardKey, value: f32) {
+        self.active_pad_axes.retain(|entry| *entry != (pad, axis));
+        if value != 0.0 {
+            self.active_pad_axes.push((pad, axis));
+        }
+        self.push(axis, StandardKeyState::Changed, StandardDevice::Gamepad(pad), false, Some(value));
+    }
+}
+
+/// Evento de acción publicado en `IgniInputSystems::EndFrame`.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct IgniActionMessage<D: Send + Sync + 'static>(pub ActionEvent<D>);
+
+/// Sets del ciclo de frame, encadenados en `PreUpdate` tras `InputSystems`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IgniInputSystems {
+    /// `begin_frame`.
+    BeginFrame,
+    /// Reenvío de entrada cruda al runtime.
+    Forward,
+    /// `end_frame` y publicación de acciones.
+    EndFrame,
+}
+
+
+// -----------------------------------------------------------------------------
+// Plugin
+// -----------------------------------------------------------------------------
+
+/// Plugin que inserta [`IgniInput`] y ejecuta el ciclo de frame del runtime.
+pub struct IgniInputPlugin<R> {
+    runtime: Mutex<Option<R>>,
+}
+
+impl<R> IgniInputPlugin<R> {
+    /// Crea el plugin con el runtime que se insertará como recurso.
+    pub fn new(runtime: R) -> Self {
+        Self { runtime: Mutex::new(Some(runtime)) }
+    }
+}
+
+impl<R: BevyInputRuntime> Plugin for IgniInputPlugin<R> {
+    fn build(&self, app: &mut App) {
+        let runtime = self.runtime.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        if let Some(runtime) = runtime {
+            app.insert_resource(IgniInput::new(runtime));
+        }
+
+        app.add_message::<KeyboardInput>()
+            .add_message::<KeyboardFocusLost>()
+            .add_message::<WindowFocused>()
+            .add_message::<MouseButtonInput>()
+            .add_message::<MouseMotion>()
+            .add_message::<MouseWheel>()
+            .add_message::<GamepadConnectionEvent>()
+            .add_message::<GamepadButtonChangedEvent>()
+            .add_message::<GamepadAxisChangedEvent>()
+            .add_message::<IgniActionMessage<ActionDevice<R>>>()
+            .configure_sets(
+                PreUpdate,
+                (IgniInputSystems::BeginFrame, IgniInputSystems::Forward, IgniInputSystems::EndFrame)
+                    .chain()
+                    .after(InputSystems),
+            )
+            .add_systems(PreUpdate, begin_frame::<R>.in_set(IgniInputSystems::BeginFrame))
+            .add_systems(
+                PreUpdate,
+                (forward_focus::<R>, forward_keyboard::<R>, forward_mouse::<R>, forward_gamepads::<R>)
+                    .chain()
+                    .in_set(IgniInputSystems::Forward),
+            )
+            .add_systems(PreUpdate, end_frame::<R>.in_set(IgniInputSystems::EndFrame));
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Sistemas
+// -----------------------------------------------------------------------------
+
+fn begin_frame<R: BevyInputRuntime>(mut input: ResMut<IgniInput<R>>) {
+    input.runtime.begin_frame();
+}
+
+/// Emite `FocusLost` cuando ninguna ventana conserva el foco y `FocusGained`
+/// cuando alguna lo recupera; un cambio de foco entre ventanas de la app no
+/// emite nada.
+fn forward_focus<R: BevyInputRuntime>(
+    mut input: ResMut<IgniInput<R>>,
+    mut focus_lost: MessageReader<KeyboardFocusLost>,
+    mut windows: MessageReader<WindowFocused>,
+) {
+    let mut changed = false;
+    if focus_lost.read().count() > 0 {
+        input.focused_windows.clear();
+        changed = true;
+    }
+    for message in windows.read() {
+        input.focused_windows.retain(|window| *window != message.window);
+        if message.focused {
+            input.focused_windows.push(message.window);
+        }
+        changed = true;
+    }
+
+    let focused = !input.focused_windows.is_empty();
+    if changed && focused != input.focused {
+        input.focused = focused;
+        let signal = if focused { RawSignal::FocusGained } else { RawSignal::FocusLost };
+        input.runtime.push_signal(signal);
+    }
+}
+
+fn forward_keyboard<R: BevyInputRuntime>(mut input: ResMut<IgniInput<R>>, mut keys: MessageReader<KeyboardInput>) {
+    for message in keys.read() {
+        let pressed = message.state == ButtonState::Pressed;
+        if let Some(text) = message.text.as_deref().filter(|_| pressed) {
+            let printable: String = text.chars().filter(|c| !c.is_control()).collect();
+            if !printable.is_empty() {
+                input.runtime.push_text_event(TextInputEvent::Commit(printable));
+            }
+        }
+        let Some(key) = standard_key_for_code(message.key_code) else { continue };
+        let state = if pressed { StandardKeyState::Pressed } else { StandardKeyState::Released };
+        input.push(key, state, StandardDevice::Keyboard, message.repeat, None);
+    }
+}
+
+fn forward_mouse<R: BevyInputRuntime>(
+    mut input: ResMut<IgniInput<R>>,
+    mut buttons: MessageReader<MouseButtonInput>,
+    mut motion: MessageReader<MouseMotion>,
+    mut wheel: MessageReader<MouseWheel>,
+) {
+    for message in buttons.read() {
+        let Some(key) = standard_mouse_button(message.button) else { continue };
+        let state = match message.state {
+            ButtonState::Pressed => StandardKeyState::Pressed,
+            ButtonState::Released => StandardKeyState::Released,
+        };
+        input.push(key, state, StandardDevice::Mouse, false, None);
+    }
+    for message in motion.read() {
+        if message.delta.x != 0.0 {
+            input.push(StandardKey::MouseMotionX, StandardKeyState::Changed, StandardDevice::Mouse, false, Some(message.delta.x));
+        }
+        if message.delta.y != 0.0 {
+            input.push(StandardKey::MouseMotionY, StandardKeyState::Changed, StandardDevice::Mouse, false, Some(message.delta.y));
+        }
+    }
+    for message in wheel.read() {
+        let scale = match message.unit {
+            MouseScrollUnit::Line => 1.0,
+            MouseScrollUnit::Pixel => 1.0 / MouseScrollUnit::SCROLL_UNIT_CONVERSION_FACTOR,
+        };
+        if message.y != 0.0 {
+            input.push(StandardKey::MouseWheel, StandardKeyState::Changed, StandardDevice::Mouse, false, Some(message.y * scale));
+        }
+        if message.x != 0.0 {
+            input.push(
+                StandardKey::MouseWheelHorizontal,
+                StandardKeyState::Changed,
+                StandardDevice::Mouse,
+                false,
+                Some(message.x * scale),
+            );
+        }
+    }
+}
+
+/// Reenvía conexiones, botones y ejes de mando.
+///
+/// Cada tipo de mensaje se lee de su propia cola, así que el orden relativo
+/// entre ellos se pierde. Los botones y ejes de un mando desconectado en este
+/// mismo frame se descartan: reenviarlos volvería a conectar el mando (un
+/// `Connected` espurio y un índice fantasma).
+fn forward_gamepads<R: BevyInputRuntime>(
+    mut input: ResMut<IgniInput<R>>,
+    mut disconnected: Local<Vec<Entity>>,
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    mut buttons: MessageReader<GamepadButtonChangedEvent>,
+    mut axes: MessageReader<GamepadAxisChangedEvent>,
+) {
+    disconnected.clear();
+    for message in connections.read() {
+        match message.connection {
+            GamepadConnection::Connected { .. } => {
+                disconnected.retain(|entity| *entity != message.gamepad);
+                input.connect_pad(message.gamepad);
+            }
+            GamepadConnection::Disconnected => {
+                disconnected.push(message.gamepad);
+                input.disconnect_pad(message.gamepad);
+            }
+        }
+    }
+    for message in buttons.read() {
+        if disconnected.contains(&message.entity) {
+            continue;
+        }
+        let pad = input.connect_pad(message.entity);
+        if let Some(axis) = trigger_axis(message.button) {
+            input.set_pad_axis(pad, axis, message.value);
+        }
+        let Some(key) = standard_gamepad_button(message.button) else { continue };
+        // Bevy informa cada cambio de valor; solo se reenvían las transiciones.
+        let pressed = message.state == ButtonState::Pressed;
+        let held = input.held_pad_buttons.contains(&(pad, key));
+        if pressed && !held {
+            input.held_pad_buttons.push((pad, key));
+            input.push(key, StandardKeyState::Pressed, StandardDevice::Gamepad(pad), false, None);
+        } else if !pressed && held {
+            input.held_pad_buttons.retain(|entry| *entry != (pad, key));
+            input.push(key, StandardKeyState::Released, StandardDevice::Gamepad(pad), false, None);
+        }
+    }
+    for message in axes.read() {
+        if disconnected.contains(&message.entity) {
+            continue;
+        }
+        let pad = input.connect_pad(message.entity);
+        if let Some(axis) = standard_gamepad_axis(message.axis) {
+            input.set_pad_axis(pad, axis, message.value);
+        }
+    }
+}
+
+fn end_frame<R: BevyInputRuntime>(
+    mut input: ResMut<IgniInput<R>>,
+    mut actions: MessageWriter<IgniActionMessage<ActionDevice<R>>>,
+) {
+    input.runtime.end_frame();
+    actions.write_batch(input.runtime.drain_action_events().into_iter().map(IgniActionMessage));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy_ecs::message::Messages;
+    use bevy_input::keyboard::Key;
+
+    use crate::{
+        contracts::game_contract::GameContract,
+        layers::{action_events::ActionPhase, processing_layer::ProcessingLayerState},
+        testing::{TestMapping, TestRuntime},
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        let mapping = TestMapping::default().with_context("game", &[("Jump", &[StandardKey::Space])]);
+        app.add_plugins(IgniInputPlugin::new(TestRuntime::new(mapping)));
+        app
+    }
+
+    fn runtime(app: &App) -> &TestRuntime {
+        app.world().resource::<IgniInput<TestRuntime>>().runtime()
+    }
+
+    fn frame_events(app: &App) -> Vec<(StandardKey, StandardKeyState, StandardDevice)> {
+        let processing = runtime(app).game_layer().processing_layer();
+        processing.frame_events().iter().map(|e| (e.key, e.state, e.device)).collect()
+    }
+
+    fn pad_id(app: &App, gamepad: Entity) -> Option<u32> {
+        app.world().resource::<IgniInput<TestRuntime>>().pad_id(gamepad)
+    }
+
+    fn published(app: &App) -> Vec<ActionEvent<StandardDevice>> {
+        let messages = app.world().resource::<Messages<IgniActionMessage<StandardDevice>>>();
+        messages.iter_current_update_messages().map(|message| message.0.clone()).collect()
+    }
+
+    fn key(app: &mut App, code: KeyCode, logical_key: Key, state: ButtonState) {
+        let window = app.world_mut().spawn_empty().id();
+        app.world_mut().write_message(KeyboardInput { key_code: code, logical_key, state, text: None, repeat: false, window });
+    }
+
+    fn window_focus(app: &mut App, window: Entity, focused: bool) {
+        app.world_mut().write_message(WindowFocused { window, focused });
+    }
+
+    fn connection(app: &mut App, gamepad: Entity, connection: GamepadConnection) {
+        app.world_mut().write_message(GamepadConnectionEvent::new(gamepad, connection));
+    }
+
+    fn connected() -> GamepadConnection {
+        GamepadConnection::Connected { name: "pad".into(), vendor_id: None, product_id: None }
+    }
+
+    fn button(app: &mut App, gamepad: Entity, button: GamepadButton, pressed: bool) {
+        let (state, value) = if pressed { (ButtonState::Pressed, 1.0) } else { (ButtonState::Released, 0.0) };
+        app.world_mut().write_message(GamepadButtonChangedEvent::new(gamepad, button, state, value));
+    }
+
+    #[test]
+    fn keyboard_press_becomes_action_message() {
+        let mut app = app();
+        key(&mut app, KeyCode::Space, Key::Space, ButtonState::Pressed);
+        app.update();
+
+        let actions = published(&app);
+        let phases: Vec<_> = actions.iter().map(|action| (&*action.action, action.phase)).collect();
+        assert_eq!(phases, [("Jump", ActionPhase::Started), ("Jump", ActionPhase::Performed)]);
+        assert!(actions.iter().all(|action| action.device == Some(StandardDevice::Keyboard)));
+
+        app.update();
+        assert!(published(&app).is_empty());
+    }
+
+    #[test]
+    fn window_focus_is_forwarded_both_ways() {
+        let mut app = app();
+        let (first, second) = (app.world_mut().spawn_empty().id(), app.world_mut().spawn_empty().id());
+        window_focus(&mut app, first, true);
+        app.update();
+        assert!(runtime(&app).signals().is_empty());
+
+        key(&mut app, KeyCode::Space, Key::Space, ButtonState::Pressed);
+        app.update();
+        window_focus(&mut app, first, false);
+        window_focus(&mut app, second, true);
+        app.update();
+        assert!(runtime(&app).signals().is_empty());
+
+        window_focus(&mut app, second, false);
+        app.update();
+        let processing = runtime(&app).game_layer().processing_layer();
+        assert_eq!(runtime(&app).signals(), [RawSignal::FocusLost]);
+        assert!(!processing.has_focus());
+        assert!(!processing.is_pressed(&StandardKey::Space));
+        assert!(published(&app).iter().any(|action| action.phase == ActionPhase::Canceled));
+
+        window_focus(&mut app, first, true);
+        app.update();
+        assert_eq!(runtime(&app).signals(), [RawSignal::FocusLost, RawSignal::FocusGained]);
+        assert!(runtime(&app).game_layer().processing_layer().has_focus());
+    }
+
+    #[test]
+    fn keyboard_focus_lost_counts_as_losing_every_window() {
+        let mut app = app();
+        let window = app.world_mut().spawn_empty().id();
+        window_focus(&mut app, window, true);
+        app.update();
+        app.world_mut().write_message(KeyboardFocusLost);
+        app.update();
+        assert_eq!(runtime(&app).signals(), [RawSignal::FocusLost]);
+
+        window_focus(&mut app, window, true);
+        app.update();
+        assert_eq!(runtime(&app).signals(), [RawSignal::FocusLost, RawSignal::FocusGained]);
+    }
+
+    #[test]
+    fn gamepads_get_lowest_free_index() {
+        let mut app = app();
+        let first = app.world_mut().spawn_empty().id();
+        let second = app.world_mut().spawn_empty().id();
+        let third = app.world_mut().spawn_empty().id();
+
+        connection(&mut app, first, connected());
+        connection(&mut app, second, connected());
+        app.update();
+        assert_eq!((pad_id(&app, first), pad_id(&app, second)), (Some(0), Some(1)));
+
+        connection(&mut app, first, GamepadConnection::Disconnected);
+        app.update();
+        assert_eq!(pad_id(&app, first), None);
+
+        connection(&mut app, third, connected());
+        app.update();
+        assert_eq!(pad_id(&app, third), Some(0));
+        assert_eq!(
+            runtime(&app).device_changes(),
+            [
+                (StandardDevice::Gamepad(0), DeviceChange::Connected),
+                (StandardDevice::Gamepad(1), DeviceChange::Connected),
+                (StandardDevice::Gamepad(0), DeviceChange::Disconnected),
+                (StandardDevice::Gamepad(0), DeviceChange::Connected),
+            ]
+        );
+    }
+
+    #[test]
+    fn disconnect_releases_held_buttons() {
+        let mut app = app();
+        let pad = app.world_mut().spawn_empty().id();
+        connection(&mut app, pad, connected());
+        button(&mut app, pad, GamepadButton::South, true);
+        app.update();
+        assert_eq!(frame_events(&app), [(StandardKey::GamepadSouth, StandardKeyState::Pressed, StandardDevice::Gamepad(0))]);
+
+        connection(&mut app, pad, GamepadConnection::Disconnected);
+        app.update();
+        assert_eq!(frame_events(&app), [(StandardKey::GamepadSouth, StandardKeyState::Released, StandardDevice::Gamepad(0))]);
+        assert!(!runtime(&app).game_layer().processing_layer().is_pressed(&StandardKey::GamepadSouth));
+    }
+
+    #[test]
+    fn input_from_pad_disconnected_this_frame_is_dropped() {
+        let mut app = app();
+        let pad = app.world_mut().spawn_empty().id();
+        connection(&mut app, pad, connected());
+        app.update();
+
+        button(&mut app, pad, GamepadButton::South, true);
+        app.world_mut().write_message(GamepadAxisChangedEvent::new(pad, GamepadAxis::LeftStickX, 0.5));
+        connection(&mut app, pad, GamepadConnection::Disconnected);
+        app.update();
+
+        assert_eq!(pad_id(&app, pad), None);
+        assert!(frame_events(&app).is_empty());
+        assert_eq!(
+            runtime(&app).device_changes(),
+            [
+                (StandardDevice::Gamepad(0), DeviceChange::Connected),
+                (StandardDevice::Gamepad(0), DeviceChange::Disconnected),
+            ]
+        );
+    }
+}
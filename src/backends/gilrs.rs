@@ -0,0 +1,500 @@
+//! ---------------------------------------------------------------------------
+//! Adaptador crudo para **gilrs** (0.11): gamepads y joysticks.
+//!
+//! Traduce los eventos de gilrs al conjunto fijo de botones y ejes de gamepad
+//! de [`StandardKey`], identificando cada mando con un índice estable
+//! ([`StandardDevice::Gamepad`]):
+//!
+//! - al conectarse, un mando recibe el menor índice libre (0, 1, 2...),
+//! - al desconectarse, su índice queda libre para el siguiente mando, se
+//!   liberan sus botones presionados y sus ejes vuelven a reposo.
+//!
+//! Las conexiones y desconexiones se informan aparte, como
+//! [`DeviceChange`], para reenviarlas a
+//! [`RuntimeInputExt::push_device_change`](crate::contracts::runtime_contract::RuntimeInputExt::push_device_change).
+//!
+//! La traducción vive en [`GilrsBridge`], que no necesita un contexto de
+//! gilrs: recibe [`PadEvent`] (equivalentes a `gilrs::EventType`) y puede
+//! alimentarse con eventos sintéticos. [`GilrsRawLayer`] lo conecta a
+//! `gilrs::Gilrs`.
+//!
+//! En Linux, gilrs requiere `libudev` para compilar.
+//! ---------------------------------------------------------------------------
+
+use std::time::SystemTime;
+
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+use crate::{
+    contracts::subscription_contract::DeviceChange,
+    layers::{
+        raw_layer::RawInputLayer,
+        standard_keys::{StandardDevice, StandardKey, StandardKeyEvent, StandardKeyState},
+        timestamp::Timestamp,
+    },
+};
+
+
+// -----------------------------------------------------------------------------
+// Tablas
+// -----------------------------------------------------------------------------
+
+/// Botón estándar de un botón de gilrs.
+///
+/// `LeftTrigger` / `RightTrigger` de gilrs son los bumpers (L1 / R1);
+/// `LeftTrigger2` / `RightTrigger2`, los gatillos (L2 / R2).
+pub fn standard_button(button: Button) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match button {
+        Button::South => GamepadSouth,
+        Button::East => GamepadEast,
+        Button::North => GamepadNorth,
+        Button::West => GamepadWest,
+        Button::LeftTrigger => GamepadLeftBumper,
+        Button::RightTrigger => GamepadRightBumper,
+        Button::LeftTrigger2 => GamepadLeftTrigger,
+        Button::RightTrigger2 => GamepadRightTrigger,
+        Button::Select => GamepadSelect,
+        Button::Start => GamepadStart,
+        Button::Mode => GamepadMode,
+        Button::LeftThumb => GamepadLeftStick,
+        Button::RightThumb => GamepadRightStick,
+        Button::DPadUp => GamepadDPadUp,
+        Button::DPadDown => GamepadDPadDown,
+        Button::DPadLeft => GamepadDPadLeft,
+        Button::DPadRight => GamepadDPadRight,
+        Button::C | Button::Z | Button::Unknown => return None,
+    };
+    Some(key)
+}
+
+/// Eje estándar de un eje de gilrs.
+///
+/// `DPadX` / `DPadY` se ignoran: gilrs ya informa la cruceta como botones.
+pub fn standard_axis(axis: Axis) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match axis {
+        Axis::LeftStickX => GamepadLeftStickX,
+        Axis::LeftStickY => GamepadLeftStickY,
+        Axis::RightStickX => GamepadRightStickX,
+        Axis::RightStickY => GamepadRightStickY,
+        Axis::LeftZ => GamepadLeftTriggerAxis,
+        Axis::RightZ => GamepadRightTriggerAxis,
+        Axis::DPadX | Axis::DPadY | Axis::Unknown => return None,
+    };
+    Some(key)
+}
+
+/// Eje analógico asociado a un gatillo informado como botón (`ButtonChanged`).
+fn trigger_axis(button: Button) -> Option<StandardKey> {
+    match button {
+        Button::LeftTrigger2 => Some(StandardKey::GamepadLeftTriggerAxis),
+        Button::RightTrigger2 => Some(StandardKey::GamepadRightTriggerAxis),
+        _ => None,
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Eventos
+// -----------------------------------------------------------------------------
+
+/// Evento de un mando, equivalente a `gilrs::EventType` sin los códigos
+/// nativos (que gilrs no permite construir).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadEvent {
+    /// El mando se conectó.
+    Connected,
+    /// El mando se desconectó.
+    Disconnected,
+    /// Botón presionado.
+    ButtonPressed(Button),
+    /// Auto-repetición de un botón mantenido.
+    ButtonRepeated(Button),
+    /// Botón liberado.
+    ButtonReleased(Button),
+    /// Valor analógico de un botón, en `[0.0, 1.0]`.
+    ButtonChanged(Button, f32),
+    /// Valor de un eje, en `[-1.0, 1.0]` (Y positivo hacia arriba).
+    AxisChanged(Axis, f32),
+}
+
+impl PadEvent {
+    /// Convierte un evento de gilrs. Devuelve `None` para eventos sin
+    /// equivalente (`Dropped`, `ForceFeedbackEffectCompleted`).
+    pub fn from_gilrs(event: &EventType) -> Option<Self> {
+        let event = match *event {
+            EventType::Connected => PadEvent::Connected,
+            EventType::Disconnected => PadEvent::Disconnected,
+            EventType::ButtonPressed(button, _) => PadEvent::ButtonPressed(button),
+            EventType::ButtonRepeated(button, _) => PadEvent::ButtonRepeated(button),
+            EventType::ButtonReleased(button, _) => PadEvent::ButtonReleased(button),
+            EventType::ButtonChanged(button, value, _) => PadEvent::ButtonChanged(button, value),
+            EventType::AxisChanged(axis, value, _) => PadEvent::AxisChanged(axis, value),
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Puente
+// -----------------------------------------------------------------------------
+
+/// Traductor puro de eventos de mandos a eventos crudos estándar.
+///
+/// Los mandos se identifican por el índice de gilrs (`usize::from(GamepadId)`).
+/// Un evento de un mando desconocido lo registra como conectado.
+#[derive(Debug, Clone, Default)]
+pub struct GilrsBridge {
+    /// Índice de gilrs ocupando cada índice estable.
+    slots: Vec<Option<usize>>,
+    /// Botones presionados, por índice estable.
+    held: Vec<(u32, StandardKey)>,
+    /// Ejes fuera de reposo, por índice estable.
+    active_axes: Vec<(u32, StandardKey)>,
+    events: Vec<StandardKeyEvent>,
+    changes: Vec<(StandardDevice, DeviceChange)>,
+}
+
+impl GilrsBridge {
+    /// Crea un puente sin mandos conectados.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Índice estable asignado a un mando de gilrs, si está conectado.
+    pub fn pad_id(&self, gamepad: usize) -> Option<u32> {
+        self.slots.iter().position(|slot| *slot == Some(gamepad)).map(|index| index as u32)
+    }
+
+    /// Índices estables de los mandos conectados.
+    pub fn connected_pads(&self) -> Vec<u32> {
+        (0..self.slots.len() as u32).filter(|&pad| self.slots[pad as usize].is_some()).collect()
+    }
+
+    /// Traduce un evento de gilrs.
+    pub fn handle_gilrs(&mut self, event: &gilrs::Event, timestamp: Timestamp) {
+        if let Some(pad_event) = PadEvent::from_gilrs(&event.event) {
+            self.handle(usize::from(event.id), pad_event, timestamp);
+        }
+    }
+
+    /// Traduce un evento del mando `gamepad` (índice de gilrs).
+    pub fn handle(&mut self, gamepad: usize, event: PadEvent, timestamp: Timestamp) {
+        if event == PadEvent::Disconnected {
+            self.disconnect(gamepad, timestamp);
+            return;
+        }
+
+        let pad = self.connect(gamepad);
+        match event {
+            PadEvent::Connected | PadEvent::Disconnected => {}
+            PadEvent::ButtonPressed(button) | PadEvent::ButtonRepeated(button) => {
+                let Some(key) = standard_button(button) else { return };
+                let repeat = matches!(event, PadEvent::ButtonRepeated(_));
+                if !self.held.contains(&(pad, key)) {
+                    self.held.push((pad, key));
+                }
+                self.push(pad, key, StandardKeyState::Pressed, repeat, None, timestamp);
+            }
+            PadEvent::ButtonReleased(button) => {
+                let Some(key) = standard_button(button) else { return };
+                self.held.retain(|entry| *entry != (pad, key));
+                self.push(pad, key, StandardKeyState::Released, false, None, timestamp);
+            }
+            PadEvent::ButtonChanged(button, value) => {
+                if let Some(axis) = trigger_axis(button) {
+                    self.set_axis(pad, axis, value, timestamp);
+                }
+            }
+            PadEvent::AxisChanged(axis, value) => {
+                if let Some(axis) = standard_axis(axis) {
+                    self.set_axis(pad, axis, value, timestamp);
+                }
+            }
+        }
+    }
+
+    /// Devuelve y limpia los eventos traducidos.
+    pub fn take_events(&mut self) -> Vec<StandardKeyEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Igual que `take_events`, pero añade los eventos al final de `out`
+    /// conservando la capacidad de la cola interna.
+    pub fn take_events_into(&mut self, out: &mut Vec<StandardKeyEvent>) {
+        out.append(&mut self.events);
+    }
+
+    /// Devuelve y limpia las conexiones y desconexiones pendientes.
+    pub fn take_device_changes(&mut self) -> Vec<(StandardDevice, DeviceChange)> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Olvida todos los mandos y el estado pendiente, sin emitir eventos.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Devuelve el índice estable del mando, asignando el menor libre si es nuevo.
+    fn connect(&mut self, gamepad: usize) -> u32 {
+        if let Some(pad) = self.pad_id(gamepad) {
+            return pad;
+        }
+        let index = match self.slots.iter().position(Option::is_none) {
+            Some(index) => index,
+            None => {
+                self.slots.push(None);
+                self.slots.len() - 1
+            }
+        };
+        self.slots[index] = Some(gamepad);
+        let pad = index as u32;
+        self.changes.push((StandardDevice::Gamepad(pad), DeviceChange::Connected));
+        pad
+    }
+
+    /// Libera botones y ejes del mando y su índice estable.
+    fn disconnect(&mut self, gamepad: usize, timestamp: Timestamp) {
+        let Some(pad) = self.pad_id(gamepad) else { return };
+
+        let held: Vec<StandardKey> = self.held.iter().filter(|(p, _)| *p == pad).map(|(_, key)| *key).collect();
+        for key in held {
+            self.push(pad, key, StandardKeyState::Released, false, None, timestamp);
+        }
+        let axes: Vec<StandardKey> = self.active_axes.iter().filter(|(p, _)| *p == pad).map(|(_, key)| *key).collect();
+        for axis in axes {
+            self.push(pad, axis, StandardKeyState::Changed, false, Some(0.0), timestamp);
+        }
+
+        self.held.retain(|(p, _)| *p != pad);
+        self.active_axes.retain(|(p, _)| *p != pad);
+        self.slots[pad as usize] = None;
+        self.changes.push((StandardDevice::Gamepad(pad), DeviceChange::Disconnected));
+    }
+
+    fn set_axis(&mut self, pad: u32, axis: StandardKey, value: f32, timestamp: Timestamp) {
+        self.active_axes.retain(|entry| *entry != (pad, axis));
+        if value != 0.0 {
+            self.active_axes.push((pad, axis));
+        }
+        self.push(pad, axis, StandardKeyState::Changed, false, Some(value), timestamp);
+    }
+
+    fn push(
+        &mut self,
+        pad: u32,
+        key: StandardKey,
+        state: StandardKeyState,
+        repeat: bool,
+        value: Option<f32>,
+        timestamp: Timestamp,
+    ) {
+        self.events.push(StandardKeyEvent {
+            key,
+            state,
+            timestamp,
+            device: StandardDevice::Gamepad(pad),
+            repeat,
+            value,
+        });
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// RawInputLayer
+// -----------------------------------------------------------------------------
+
+/// Backend crudo de mandos sobre un contexto `gilrs::Gilrs`.
+///
+/// `poll_events` vacía la cola de gilrs; las conexiones se consultan con
+/// `poll_device_changes` después de cada `poll_events`.
+pub struct GilrsRawLayer {
+    gilrs: Gilrs,
+    bridge: GilrsBridge,
+}
+
+impl GilrsRawLayer {
+    /// Crea el contexto de gilrs.
+    ///
+    /// En plataformas sin soporte, el error `NotImplemented` contiene un
+    /// contexto vacío utilizable con `from_gilrs`.
+    pub fn new() -> Result<Self, Box<gilrs::Error>> {
+        Ok(Self::from_gilrs(Gilrs::new().map_err(Box::new)?))
+    }
+
+    /// Usa un contexto de gilrs ya configurado (p. ej. con `GilrsBuilder`).
+    pub fn from_gilrs(gilrs: Gilrs) -> Self {
+        Self { gilrs, bridge: GilrsBridge::new() }
+    }
+
+    /// Contexto de gilrs (vibración, nombres de mandos, mapeos SDL...).
+    pub fn gilrs(&self) -> &Gilrs {
+        &self.gilrs
+    }
+
+    /// Contexto de gilrs, mutable.
+    pub fn gilrs_mut(&mut self) -> &mut Gilrs {
+        &mut self.gilrs
+    }
+
+    /// Puente de traducción (índices estables de los mandos).
+    pub fn bridge(&self) -> &GilrsBridge {
+        &self.bridge
+    }
+
+    /// Devuelve y limpia las conexiones y desconexiones pendientes.
+    pub fn poll_device_changes(&mut self) -> Vec<(StandardDevice, DeviceChange)> {
+        self.bridge.take_device_changes()
+    }
+}
+
+impl RawInputLayer for GilrsRawLayer {
+    type KeyEvent = StandardKeyEvent;
+
+    fn poll_events(&mut self) -> Vec<Self::KeyEvent> {
+        let mut events = Vec::new();
+        self.poll_events_into(&mut events);
+        events
+    }
+
+    fn poll_events_into(&mut self, out: &mut Vec<Self::KeyEvent>) {
+        let now = Timestamp::now();
+        let wall_now = SystemTime::now();
+        while let Some(event) = self.gilrs.next_event() {
+            // Convierte el reloj de pared de gilrs a `Timestamp` por diferencia con el presente.
+            let age = wall_now.duration_since(event.time).unwrap_or_default();
+            let timestamp = now.checked_sub(age).unwrap_or(now);
+            self.bridge.handle_gilrs(&event, timestamp);
+        }
+        self.gilrs.inc();
+        self.bridge.take_events_into(out);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use StandardKeyState::{Changed, Pressed, Released};
+
+    const T: Timestamp = Timestamp::ZERO;
+
+    fn events(bridge: &mut GilrsBridge) -> Vec<(StandardKey, StandardKeyState, StandardDevice, Option<f32>)> {
+        bridge.take_events().into_iter().map(|e| (e.key, e.state, e.device, e.value)).collect()
+    }
+
+    #[test]
+    fn pads_get_lowest_free_index() {
+        let mut bridge = GilrsBridge::new();
+        bridge.handle(7, PadEvent::Connected, T);
+        bridge.handle(3, PadEvent::Connected, T);
+        assert_eq!((bridge.pad_id(7), bridge.pad_id(3)), (Some(0), Some(1)));
+
+        // Un evento de un mando desconocido también lo conecta.
+        bridge.handle(9, PadEvent::ButtonPressed(Button::South), T);
+        assert_eq!(bridge.pad_id(9), Some(2));
+        assert_eq!(bridge.connected_pads(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn index_is_reused_after_disconnect() {
+        let mut bridge = GilrsBridge::new();
+        bridge.handle(0, PadEvent::Connected, T);
+        bridge.handle(1, PadEvent::Connected, T);
+        bridge.handle(0, PadEvent::Disconnected, T);
+        assert_eq!(bridge.pad_id(0), None);
+        assert_eq!(bridge.connected_pads(), [1]);
+
+        bridge.handle(5, PadEvent::Connected, T);
+        assert_eq!(bridge.pad_id(5), Some(0));
+        assert_eq!(
+            bridge.take_device_changes(),
+            [
+                (StandardDevice::Gamepad(0), DeviceChange::Connected),
+                (StandardDevice::Gamepad(1), DeviceChange::Connected),
+                (StandardDevice::Gamepad(0), DeviceChange::Disconnected),
+                (StandardDevice::Gamepad(0), DeviceChange::Connected),
+            ]
+        );
+    }
+
+    #[test]
+    fn disconnect_releases_held_buttons_and_zeroes_axes() {
+        let mut bridge = GilrsBridge::new();
+        bridge.handle(0, PadEvent::Connected, T);
+        bridge.handle(1, PadEvent::Connected, T);
+        bridge.handle(1, PadEvent::ButtonPressed(Button::South), T);
+        bridge.handle(1, PadEvent::ButtonPressed(Button::East), T);
+        bridge.handle(1, PadEvent::ButtonReleased(Button::East), T);
+        bridge.handle(1, PadEvent::AxisChanged(Axis::LeftStickX, 0.5), T);
+        bridge.handle(1, PadEvent::AxisChanged(Axis::RightStickY, 0.25), T);
+        bridge.handle(1, PadEvent::AxisChanged(Axis::RightStickY, 0.0), T);
+        bridge.handle(0, PadEvent::ButtonPressed(Button::North), T);
+        bridge.take_events();
+
+        bridge.handle(1, PadEvent::Disconnected, T);
+        let pad = StandardDevice::Gamepad(1);
+        assert_eq!(
+            events(&mut bridge),
+            [(StandardKey::GamepadSouth, Released, pad, None), (StandardKey::GamepadLeftStickX, Changed, pad, Some(0.0))]
+        );
+
+        // El otro mando conserva su estado.
+        bridge.handle(0, PadEvent::Disconnected, T);
+        assert_eq!(events(&mut bridge), [(StandardKey::GamepadNorth, Released, StandardDevice::Gamepad(0), None)]);
+    }
+
+    #[test]
+    fn disconnecting_unknown_pad_is_ignored() {
+        let mut bridge = GilrsBridge::new();
+        bridge.handle(4, PadEvent::Disconnected, T);
+        assert!(bridge.take_events().is_empty());
+        assert!(bridge.take_device_changes().is_empty());
+    }
+
+    #[test]
+    fn trigger_button_changes_drive_trigger_axis() {
+        let mut bridge = GilrsBridge::new();
+        bridge.handle(0, PadEvent::ButtonChanged(Button::LeftTrigger2, 0.4), T);
+        bridge.handle(0, PadEvent::ButtonChanged(Button::RightTrigger2, 1.0), T);
+        bridge.handle(0, PadEvent::ButtonPressed(Button::RightTrigger2), T);
+        // Los bumpers no tienen eje.
+        bridge.handle(0, PadEvent::ButtonChanged(Button::LeftTrigger, 1.0), T);
+
+        let pad = StandardDevice::Gamepad(0);
+        assert_eq!(
+            events(&mut bridge),
+            [
+                (StandardKey::GamepadLeftTriggerAxis, Changed, pad, Some(0.4)),
+                (StandardKey::GamepadRightTriggerAxis, Changed, pad, Some(1.0)),
+                (StandardKey::GamepadRightTrigger, Pressed, pad, None),
+            ]
+        );
+
+        bridge.handle(0, PadEvent::Disconnected, T);
+        assert_eq!(
+            events(&mut bridge),
+            [
+                (StandardKey::GamepadRightTrigger, Released, pad, None),
+                (StandardKey::GamepadLeftTriggerAxis, Changed, pad, Some(0.0)),
+                (StandardKey::GamepadRightTriggerAxis, Changed, pad, Some(0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_button_is_flagged() {
+        let mut bridge = GilrsBridge::new();
+        bridge.handle(0, PadEvent::ButtonPressed(Button::DPadUp), T);
+        bridge.handle(0, PadEvent::ButtonRepeated(Button::DPadUp), T);
+        let repeats: Vec<bool> = bridge.take_events().iter().map(|e| e.repeat).collect();
+        assert_eq!(repeats, [false, true]);
+    }
+}
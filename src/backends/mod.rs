@@ -0,0 +1,37 @@
+//! # IGNI Input Core — Backends de referencia
+//!
+//! Implementaciones opcionales de [`RawInputLayer`](crate::layers::raw_layer::RawInputLayer),
+//! en su mayoría sobre el conjunto estándar de teclas
+//! ([`StandardKey`](crate::layers::standard_keys::StandardKey)). Los adaptadores
+//! de crates externos conservan además los códigos originales del backend.
+//!
+//! Ninguna se compila por defecto: cada backend se habilita con su propia
+//! feature, de modo que el núcleo de contratos sigue sin dependencias
+//! obligatorias ni implementaciones.
+//!
+//! | Módulo       | Feature    | Fuente de eventos                        |
+//! |--------------|------------|------------------------------------------|
+//! | [`scripted`] | `scripted` | Script de texto con reloj simulado (tests) |
+//! | [`evdev`]    | `evdev`    | Linux `/dev/input/event*`                |
+//! | [`terminal`] | `terminal` | Secuencias ANSI / xterm / kitty de un terminal |
+//! | [`winit`]    | `winit`    | `WindowEvent` / `DeviceEvent` de winit 0.30 |
+//! | [`gilrs`]    | `gilrs`    | Gamepads vía gilrs 0.11                  |
+//! | [`bevy`]     | `bevy`     | Plugin de Bevy 0.18 (mensajes de entrada y acciones) |
+
+#[cfg(feature = "scripted")]
+pub mod scripted;
+
+#[cfg(feature = "evdev")]
+pub mod evdev;
+
+#[cfg(feature = "terminal")]
+pub mod terminal;
+
+#[cfg(feature = "winit")]
+pub mod winit;
+
+#[cfg(feature = "gilrs")]
+pub mod gilrs;
+
+#[cfg(feature = "bevy")]
+pub mod bevy;
@@ -0,0 +1,554 @@
+//! ---------------------------------------------------------------------------
+//! **ScriptedRawLayer**: backend crudo guiado por un script de texto.
+//!
+//! Pensado para tests de integración y escenarios reproducibles sin
+//! ningún dispositivo ni API del sistema operativo. El backend lleva su
+//! propio reloj simulado: cada llamada a `poll_events` es un frame y
+//! avanza el reloj `frame_duration`, sin consultar `Timestamp::now`.
+//!
+//! ---------------------------------------------------------------------------
+//! ## Formato del script
+//!
+//! Sentencias separadas por `;` o saltos de línea. `#` inicia un comentario.
+//!
+//! ```text
+//! frame 3: press Space       # posiciona el cursor al inicio del frame 3
+//! frame 10: release Space
+//! wait 200ms                 # avanza el cursor 200 ms
+//! tap Enter                  # pulsa y suelta en el mismo instante
+//! repeat A                   # auto-repetición del sistema
+//! focus_lost; focus_gained; suspend; resume
+//! text "hola"                # texto confirmado
+//! ```
+//!
+//! - `frame N:` mueve el cursor temporal al inicio del frame `N` (base 0) y
+//!   ejecuta la sentencia que le sigue, si existe.
+//! - `wait <duración>` avanza el cursor (`us`, `ms`, `s`; admite decimales).
+//! - `press`, `release`, `tap` y `repeat` reciben una [`StandardKey`] por
+//!   identificador (`shift_left`) o nombre (`ShiftLeft`).
+//!
+//! Cada evento se entrega en el frame cuyo intervalo contiene su instante,
+//! y dentro del frame en el orden del script. Los timestamps parten de
+//! [`Timestamp::ZERO`] (configurable con `with_base_timestamp`), por lo que
+//! dos ejecuciones del mismo script producen exactamente los mismos eventos.
+//!
+//! ## Orden de consulta
+//!
+//! `poll_events` entrega el frame actual **y avanza** al siguiente.
+//! `poll_signals` y `poll_text` solo leen el frame actual, por lo que deben
+//! llamarse antes que `poll_events` en cada frame (el mismo orden que el
+//! flujo típico de `RuntimeInputExt`).
+//!
+//! ```ignore
+//! // Frame de 1/60 s: el press cae en el frame 1 y el release,
+//! // 50 ms después (66,7 ms), en el frame 3.
+//! let mut raw = ScriptedRawLayer::parse("frame 1: press Space; wait 50ms; release Space")?;
+//! assert!(raw.poll_events().is_empty());      // frame 0
+//! let press = raw.poll_events();              // frame 1: press
+//! assert!(raw.poll_events().is_empty());      // frame 2
+//! let release = raw.poll_events();            // frame 3: release
+//! ```
+//! ---------------------------------------------------------------------------
+
+use std::{error::Error, fmt, time::Duration};
+
+use crate::layers::{
+    raw_layer::{RawInputLayer, RawSignal, TextInputEvent},
+    standard_keys::{KeyGroup, StandardDevice, StandardKey, StandardKeyEvent, StandardKeyState},
+    timestamp::Timestamp,
+};
+
+
+/// Duración de frame por defecto (60 Hz).
+pub const DEFAULT_FRAME_DURATION: Duration = Duration::from_micros(16_667);
+
+
+/// Error de análisis de un script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError {
+    /// Línea (base 1) de la sentencia inválida.
+    pub line: usize,
+    /// Sentencia inválida, sin espacios alrededor.
+    pub statement: String,
+    /// Descripción del problema.
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} (`{}`)", self.line, self.message, self.statement)
+    }
+}
+
+impl Error for ScriptError {}
+
+
+/// Contenido de un paso del script.
+#[derive(Debug, Clone, PartialEq)]
+enum ScriptedItem {
+    Key { key: StandardKey, state: StandardKeyState, repeat: bool },
+    Signal(RawSignal),
+    Text(String),
+}
+
+/// Paso del script.
+///
+/// La posición se guarda como índice de frame más desplazamiento para poder
+/// reubicarla si cambia la duración del frame.
+#[derive(Debug, Clone, PartialEq)]
+struct ScriptedStep {
+    frame: u64,
+    offset: Duration,
+    item: ScriptedItem,
+}
+
+impl ScriptedStep {
+    /// Instante del paso relativo al inicio de la simulación.
+    fn at(&self, frame_duration: Duration) -> Duration {
+        frame_start(self.frame, frame_duration) + self.offset
+    }
+}
+
+
+/// Backend crudo que reproduce un script con reloj simulado.
+#[derive(Debug, Clone)]
+pub struct ScriptedRawLayer {
+    steps: Vec<ScriptedStep>,
+    /// Índice del primer paso aún no entregado (eventos de tecla).
+    next_event: usize,
+    /// Índice del primer paso aún no entregado (señales).
+    next_signal: usize,
+    /// Índice del primer paso aún no entregado (texto).
+    next_text: usize,
+    frame: u64,
+    frame_duration: Duration,
+    base: Timestamp,
+}
+
+impl ScriptedRawLayer {
+    /// Analiza un script.
+    ///
+    /// Los timestamps son `base + tiempo simulado`, con `base` igual a
+    /// [`Timestamp::ZERO`] salvo que se indique otra con `with_base_timestamp`.
+    pub fn parse(script: &str) -> Result<Self, ScriptError> {
+        let mut steps = parse_script(script)?;
+        sort_steps(&mut steps, DEFAULT_FRAME_DURATION);
+        Ok(Self {
+            steps,
+            next_event: 0,
+            next_signal: 0,
+            next_text: 0,
+            frame: 0,
+            frame_duration: DEFAULT_FRAME_DURATION,
+            base: Timestamp::ZERO,
+        })
+    }
+
+    /// Cambia la duración simulada de cada frame.
+    ///
+    /// Los pasos con `frame N:` se reubican según la nueva duración, por lo
+    /// que debe llamarse antes del primer `poll_events`.
+    pub fn with_frame_duration(mut self, frame_duration: Duration) -> Self {
+        self.frame_duration = frame_duration;
+        sort_steps(&mut self.steps, frame_duration);
+        self
+    }
+
+    /// Fija el instante base de los timestamps (por defecto `Timestamp::ZERO`).
+    ///
+    /// Útil para alinear el script con otro reloj (p. ej. `Timestamp::now()`).
+    pub fn with_base_timestamp(mut self, base: Timestamp) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Número de frames ya entregados.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Tiempo simulado transcurrido (inicio del siguiente frame).
+    pub fn elapsed(&self) -> Duration {
+        frame_start(self.frame, self.frame_duration)
+    }
+
+    /// Instante simulado actual (`base + elapsed`).
+    pub fn now(&self) -> Timestamp {
+        self.base + self.elapsed()
+    }
+
+    /// Indica si todos los pasos del script ya se entregaron.
+    pub fn is_finished(&self) -> bool {
+        self.next_event == self.steps.len()
+            && self.next_signal == self.steps.len()
+            && self.next_text == self.steps.len()
+    }
+
+    /// Fin (exclusivo) del frame que se está entregando.
+    fn frame_end(&self) -> Duration {
+        frame_start(self.frame + 1, self.frame_duration)
+    }
+
+    /// Recorre los pasos pendientes desde `from` hasta el fin del frame actual.
+    fn due_steps(&self, from: usize) -> impl Iterator<Item = &ScriptedStep> {
+        let end = self.frame_end();
+        self.steps[from..].iter().take_while(move |step| step.at(self.frame_duration) < end)
+    }
+}
+
+impl RawInputLayer for ScriptedRawLayer {
+    type KeyEvent = StandardKeyEvent;
+
+    /// Entrega los eventos de tecla del frame actual y avanza un frame.
+    ///
+    /// `poll_signals` y `poll_text` del mismo frame deben llamarse antes
+    /// que `poll_events`.
+    fn poll_events(&mut self) -> Vec<Self::KeyEvent> {
+        let mut events = Vec::new();
+        self.poll_events_into(&mut events);
+        events
+    }
+
+    fn poll_events_into(&mut self, out: &mut Vec<Self::KeyEvent>) {
+        let mut consumed = 0;
+        for step in self.due_steps(self.next_event) {
+            consumed += 1;
+            if let ScriptedItem::Key { key, state, repeat } = step.item {
+                out.push(StandardKeyEvent {
+                    key,
+                    state,
+                    timestamp: self.base + step.at(self.frame_duration),
+                    device: device_for(key),
+                    repeat,
+                    value: None,
+                });
+            }
+        }
+        self.next_event += consumed;
+        self.frame += 1;
+    }
+
+    /// Entrega las señales del frame actual, sin avanzar.
+    ///
+    /// Debe llamarse antes que `poll_events` en el mismo frame.
+    fn poll_signals(&mut self) -> Vec<RawSignal> {
+        let due: Vec<&ScriptedStep> = self.due_steps(self.next_signal).collect();
+        let consumed = due.len();
+        let signals = due
+            .into_iter()
+            .filter_map(|step| match step.item {
+                ScriptedItem::Signal(signal) => Some(signal),
+                _ => None,
+            })
+            .collect();
+        self.next_signal += consumed;
+        signals
+    }
+
+    /// Entrega el texto del frame actual, sin avanzar.
+    ///
+    /// Debe llamarse antes que `poll_events` en el mismo frame.
+    fn poll_text(&mut self) -> Vec<TextInputEvent> {
+        let due: Vec<&ScriptedStep> = self.due_steps(self.next_text).collect();
+        let consumed = due.len();
+        let text = due
+            .into_iter()
+            .filter_map(|step| match &step.item {
+                ScriptedItem::Text(text) => Some(TextInputEvent::Commit(text.clone())),
+                _ => None,
+            })
+            .collect();
+        self.next_text += consumed;
+        text
+    }
+}
+
+fn frame_start(frame: u64, frame_duration: Duration) -> Duration {
+    frame_duration.saturating_mul(u32::try_from(frame).unwrap_or(u32::MAX))
+}
+
+fn device_for(key: StandardKey) -> StandardDevice {
+    match key.group() {
+        KeyGroup::Keyboard => StandardDevice::Keyboard,
+        KeyGroup::Mouse => StandardDevice::Mouse,
+        KeyGroup::Gamepad => StandardDevice::Gamepad(0),
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Parser
+// -----------------------------------------------------------------------------
+
+fn parse_script(script: &str) -> Result<Vec<ScriptedStep>, ScriptError> {
+    let mut steps = Vec::new();
+    let mut frame = 0;
+    let mut offset = Duration::ZERO;
+
+    for (index, line) in script.lines().enumerate() {
+        let line_no = index + 1;
+        let code = strip_comment(line);
+        for statement in split_statements(code) {
+            let statement = statement.trim();
+            if statement.is_empty() {
+                continue;
+            }
+            let err = |message: &str| ScriptError {
+                line: line_no,
+                statement: statement.to_owned(),
+                message: message.to_owned(),
+            };
+
+            let mut rest = statement;
+            if let Some(after) = strip_keyword(rest, "frame") {
+                let (number, tail) = after.split_once(':').ok_or_else(|| err("expected `:` after frame number"))?;
+                frame = number.trim().parse::<u64>().map_err(|_| err("invalid frame number"))?;
+                offset = Duration::ZERO;
+                rest = tail.trim();
+                if rest.is_empty() {
+                    continue;
+                }
+            }
+
+            if let Some(arg) = strip_keyword(rest, "wait") {
+                let wait = parse_duration(arg.trim()).ok_or_else(|| err("invalid duration"))?;
+                offset += wait;
+                continue;
+            }
+
+            let items = parse_action(rest).map_err(err)?;
+            steps.extend(items.into_iter().map(|item| ScriptedStep { frame, offset, item }));
+        }
+    }
+
+    Ok(steps)
+}
+
+/// Ordena los pasos por instante conservando el orden del script en empates.
+fn sort_steps(steps: &mut [ScriptedStep], frame_duration: Duration) {
+    steps.sort_by_key(|step| step.at(frame_duration));
+}
+
+fn parse_action(statement: &str) -> Result<Vec<ScriptedItem>, &'static str> {
+    let (verb, arg) = match statement.split_once(char::is_whitespace) {
+        Some((verb, arg)) => (verb, arg.trim()),
+        None => (statement, ""),
+    };
+    let key = || StandardKey::from_name(arg).ok_or("unknown key");
+    let item = |key, state, repeat| ScriptedItem::Key { key, state, repeat };
+
+    match verb.to_ascii_lowercase().as_str() {
+        "press" => Ok(vec![item(key()?, StandardKeyState::Pressed, false)]),
+        "release" => Ok(vec![item(key()?, StandardKeyState::Released, false)]),
+        "repeat" => Ok(vec![item(key()?, StandardKeyState::Pressed, true)]),
+        "tap" => {
+            let key = key()?;
+            Ok(vec![
+                item(key, StandardKeyState::Pressed, false),
+                item(key, StandardKeyState::Released, false),
+            ])
+        }
+        "focus_lost" => Ok(vec![ScriptedItem::Signal(RawSignal::FocusLost)]),
+        "focus_gained" => Ok(vec![ScriptedItem::Signal(RawSignal::FocusGained)]),
+        "suspend" => Ok(vec![ScriptedItem::Signal(RawSignal::Suspended)]),
+        "resume" => Ok(vec![ScriptedItem::Signal(RawSignal::Resumed)]),
+        "text" => {
+            let text = arg
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .ok_or("text must be quoted")?;
+            Ok(vec![ScriptedItem::Text(text.to_owned())])
+        }
+        _ => Err("unknown statement"),
+    }
+}
+
+/// Elimina el comentario `#` de una línea, respetando el texto entre comillas.
+fn strip_comment(line: &str) -> &str {
+    let mut in_quotes = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '#' if !in_quotes => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Separa sentencias por `;`, respetando el texto entre comillas.
+fn split_statements(code: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in code.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                statements.push(&code[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(&code[start..]);
+    statements
+}
+
+/// Devuelve el resto de `statement` si empieza por la palabra `keyword`.
+fn strip_keyword<'a>(statement: &'a str, keyword: &str) -> Option<&'a str> {
+    let head = statement.get(..keyword.len())?;
+    let rest = &statement[keyword.len()..];
+    let boundary = rest.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || c == ':');
+    (head.eq_ignore_ascii_case(keyword) && boundary).then_some(rest)
+}
+
+fn parse_duration(text: &str) -> Option<Duration> {
+    let split = text.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number.parse().ok()?;
+    let seconds = match unit.trim() {
+        "us" => value / 1_000_000.0,
+        "ms" => value / 1_000.0,
+        "s" => value,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use StandardKey::*;
+    use StandardKeyState::{Pressed, Released};
+
+    fn keys(events: &[StandardKeyEvent]) -> Vec<(StandardKey, StandardKeyState, bool)> {
+        events.iter().map(|e| (e.key, e.state, e.repeat)).collect()
+    }
+
+    fn error(script: &str) -> ScriptError {
+        ScriptedRawLayer::parse(script).expect_err("el script debería ser inválido")
+    }
+
+    // -------------------------------------------------------------------------
+    // Parser
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn parses_statements_comments_and_quotes() {
+        let script = "frame 2: press shift_left; tap A   # comentario\n\
+                      wait 10ms\n\
+                      text \"a;b # c\"\n\
+                      REPEAT Space; focus_lost";
+        let steps = parse_script(script).unwrap();
+        let items: Vec<_> = steps.iter().map(|s| (s.frame, s.offset, s.item.clone())).collect();
+        let key = |key, state, repeat| ScriptedItem::Key { key, state, repeat };
+        let ms = Duration::from_millis;
+        assert_eq!(
+            items,
+            [
+                (2, ms(0), key(ShiftLeft, Pressed, false)),
+                (2, ms(0), key(A, Pressed, false)),
+                (2, ms(0), key(A, Released, false)),
+                (2, ms(10), ScriptedItem::Text("a;b # c".to_owned())),
+                (2, ms(10), key(Space, Pressed, true)),
+                (2, ms(10), ScriptedItem::Signal(RawSignal::FocusLost)),
+            ]
+        );
+    }
+
+    #[test]
+    fn frame_resets_wait_offset() {
+        let steps = parse_script("wait 1s; press A; frame 3: release A").unwrap();
+        assert_eq!((steps[0].frame, steps[0].offset), (0, Duration::from_secs(1)));
+        assert_eq!((steps[1].frame, steps[1].offset), (3, Duration::ZERO));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250us"), Some(Duration::from_micros(250)));
+        assert_eq!(parse_duration("1.5ms"), Some(Duration::from_micros(1_500)));
+        assert_eq!(parse_duration("2 s"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("5min"), None);
+    }
+
+    #[test]
+    fn reports_errors_with_line() {
+        let err = error("press A\n\nfly away");
+        assert_eq!((err.line, err.statement.as_str(), err.message.as_str()), (3, "fly away", "unknown statement"));
+
+        assert_eq!(error("press NotAKey").message, "unknown key");
+        assert_eq!(error("frame x: press A").message, "invalid frame number");
+        assert_eq!(error("frame 3 press A").message, "expected `:` after frame number");
+        assert_eq!(error("wait soon").message, "invalid duration");
+        assert_eq!(error("text hola").message, "text must be quoted");
+    }
+
+    // -------------------------------------------------------------------------
+    // Escenarios
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn events_land_in_the_frame_containing_their_instant() {
+        let mut raw = ScriptedRawLayer::parse("frame 1: press Space; wait 50ms; release Space").unwrap();
+        assert!(raw.poll_events().is_empty());
+        assert_eq!(keys(&raw.poll_events()), [(Space, Pressed, false)]);
+        assert!(raw.poll_events().is_empty());
+        assert_eq!(keys(&raw.poll_events()), [(Space, Released, false)]);
+        assert_eq!(raw.frame(), 4);
+    }
+
+    #[test]
+    fn timestamps_are_stepped_and_deterministic() {
+        let script = "frame 2: press A; wait 5ms; release A";
+        let run = || {
+            let mut raw = ScriptedRawLayer::parse(script).unwrap().with_frame_duration(Duration::from_millis(10));
+            (0..3).flat_map(|_| raw.poll_events()).collect::<Vec<_>>()
+        };
+        let events = run();
+        let timestamps: Vec<_> = events.iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, [Timestamp::from_millis(20), Timestamp::from_millis(25)]);
+        assert_eq!(events, run());
+    }
+
+    #[test]
+    fn base_timestamp_offsets_events() {
+        let base = Timestamp::from_millis(1_000);
+        let mut raw = ScriptedRawLayer::parse("tap Enter").unwrap().with_base_timestamp(base);
+        assert!(raw.poll_events().iter().all(|e| e.timestamp == base));
+        assert_eq!(raw.now(), base + DEFAULT_FRAME_DURATION);
+    }
+
+    #[test]
+    fn frame_duration_relocates_frames() {
+        let mut raw = ScriptedRawLayer::parse("frame 2: tap B").unwrap().with_frame_duration(Duration::from_millis(100));
+        assert!(raw.poll_events().is_empty());
+        assert!(raw.poll_events().is_empty());
+        let events = raw.poll_events();
+        assert_eq!(keys(&events), [(B, Pressed, false), (B, Released, false)]);
+        assert_eq!(events[0].timestamp, Timestamp::from_millis(200));
+    }
+
+    #[test]
+    fn signals_and_text_are_read_before_advancing() {
+        let mut raw = ScriptedRawLayer::parse("frame 1: focus_lost; text \"hola\"; press A").unwrap();
+        assert!(raw.poll_signals().is_empty());
+        assert!(raw.poll_text().is_empty());
+        assert!(raw.poll_events().is_empty());
+
+        assert_eq!(raw.poll_signals(), [RawSignal::FocusLost]);
+        assert_eq!(raw.poll_text(), [TextInputEvent::Commit("hola".to_owned())]);
+        assert_eq!(keys(&raw.poll_events()), [(A, Pressed, false)]);
+        assert!(raw.is_finished());
+    }
+
+    #[test]
+    fn devices_follow_key_group() {
+        let mut raw = ScriptedRawLayer::parse("press A; press MouseLeft; press GamepadSouth").unwrap();
+        let devices: Vec<_> = raw.poll_events().iter().map(|e| e.device).collect();
+        assert_eq!(devices, [StandardDevice::Keyboard, StandardDevice::Mouse, StandardDevice::Gamepad(0)]);
+    }
+}
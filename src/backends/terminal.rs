@@ -0,0 +1,981 @@
+//! ---------------------------------------------------------------------------
+//! Backend crudo para **terminales** (secuencias ANSI / xterm).
+//!
+//! Decodifica los bytes que un terminal en modo *raw* envía por su entrada
+//! estándar y los convierte en [`StandardKeyEvent`]:
+//!
+//! - caracteres imprimibles y combinaciones `Ctrl` / `Alt` (`ESC` + tecla),
+//! - secuencias CSI (`ESC [`) y SS3 (`ESC O`) de flechas, navegación y
+//!   teclas de función, con su parámetro de modificadores (`ESC [1;5A`),
+//! - el **protocolo de teclado de kitty** (`ESC [ código ; mods:evento u`),
+//!   con eventos de pulsación, repetición y liberación,
+//! - reportes de mouse **SGR** (`ESC [< b ; x ; y M|m`): botones, rueda y
+//!   movimiento (en celdas),
+//! - reportes de foco (`ESC [I` / `ESC [O`) como [`RawSignal`],
+//! - pegado entre corchetes (`ESC [200~` ... `ESC [201~`), entregado
+//!   íntegro como texto, sin eventos de tecla.
+//!
+//! Los caracteres imprimibles (y el texto asociado de kitty) también se
+//! entregan como [`TextInputEvent::Commit`].
+//!
+//! ## Modificadores
+//!
+//! Los terminales informan modificadores como un parámetro de cada tecla, no
+//! como teclas propias. El decodificador sintetiza pulsaciones y liberaciones
+//! de `ShiftLeft`, `AltLeft`, `ControlLeft` y `SuperLeft` para que las capas
+//! superiores vean el mismo flujo que con un teclado físico. Con kitty y la
+//! opción *report all keys* (flag 8) las teclas modificadoras llegan como
+//! eventos propios y se respetan tal cual (izquierda o derecha). Los bits de
+//! Caps Lock y Num Lock se ignoran.
+//!
+//! ## Protocolo
+//!
+//! En modo [`KeyboardProtocol::Legacy`] el terminal no informa liberaciones:
+//! cada tecla se entrega como pulsación y liberación en el mismo instante.
+//! En modo [`KeyboardProtocol::Kitty`] se respetan los tipos de evento.
+//! Las secuencias para activar cada modo están en [`KITTY_KEYBOARD_ENABLE`],
+//! [`SGR_MOUSE_ENABLE`] y [`FOCUS_EVENTS_ENABLE`].
+//!
+//! Un `ESC` aislado es ambiguo (puede ser el inicio de una secuencia): el
+//! decodificador lo retiene hasta recibir más bytes o hasta
+//! [`TerminalDecoder::flush_pending`].
+//!
+//! El decodificador es un analizador puro de bytes; no configura el
+//! terminal (modo raw, `O_NONBLOCK`), que queda a cargo de la aplicación.
+//! ---------------------------------------------------------------------------
+
+use std::io::{self, Read};
+
+use crate::layers::{
+    raw_layer::{RawInputLayer, RawSignal, TextInputEvent},
+    standard_keys::{StandardDevice, StandardKey, StandardKeyEvent, StandardKeyState},
+    timestamp::Timestamp,
+};
+
+
+// -----------------------------------------------------------------------------
+// Secuencias de configuración del terminal
+// -----------------------------------------------------------------------------
+
+/// Activa el protocolo de kitty: desambiguación, tipos de evento, todas las
+/// teclas como secuencias y texto asociado (flags 1 | 2 | 8 | 16).
+pub const KITTY_KEYBOARD_ENABLE: &str = "\x1b[>27u";
+/// Restaura el modo de teclado previo a [`KITTY_KEYBOARD_ENABLE`].
+pub const KITTY_KEYBOARD_DISABLE: &str = "\x1b[<u";
+/// Activa reportes de mouse SGR con botones y movimiento.
+pub const SGR_MOUSE_ENABLE: &str = "\x1b[?1003h\x1b[?1006h";
+/// Desactiva los reportes de mouse.
+pub const SGR_MOUSE_DISABLE: &str = "\x1b[?1006l\x1b[?1003l";
+/// Activa los reportes de foco.
+pub const FOCUS_EVENTS_ENABLE: &str = "\x1b[?1004h";
+/// Desactiva los reportes de foco.
+pub const FOCUS_EVENTS_DISABLE: &str = "\x1b[?1004l";
+/// Activa el pegado entre corchetes.
+pub const BRACKETED_PASTE_ENABLE: &str = "\x1b[?2004h";
+/// Desactiva el pegado entre corchetes.
+pub const BRACKETED_PASTE_DISABLE: &str = "\x1b[?2004l";
+
+/// Fin de un pegado entre corchetes.
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Longitud máxima aceptada para una secuencia CSI antes de descartarla.
+const MAX_CSI_LEN: usize = 64;
+
+const MOD_SHIFT: u32 = 1;
+const MOD_ALT: u32 = 2;
+const MOD_CTRL: u32 = 4;
+const MOD_SUPER: u32 = 8;
+const MOD_HYPER: u32 = 16;
+const MOD_META: u32 = 32;
+
+/// Modificadores que forman un atajo: con alguno activo la tecla no produce
+/// texto. Shift y los bloqueos (Caps Lock = 64, Num Lock = 128) no cuentan.
+const MOD_CHORD: u32 = MOD_ALT | MOD_CTRL | MOD_SUPER | MOD_HYPER | MOD_META;
+
+/// Modificadores sintetizados, en orden de bit.
+const MODIFIERS: [(u32, StandardKey); 4] = [
+    (MOD_SHIFT, StandardKey::ShiftLeft),
+    (MOD_ALT, StandardKey::AltLeft),
+    (MOD_CTRL, StandardKey::ControlLeft),
+    (MOD_SUPER, StandardKey::SuperLeft),
+];
+
+
+/// Protocolo de teclado que emite el terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyboardProtocol {
+    /// Secuencias xterm clásicas: sin liberaciones, cada tecla es un toque.
+    #[default]
+    Legacy,
+    /// Protocolo de kitty con tipos de evento (pulsación / repetición / liberación).
+    Kitty,
+}
+
+
+// -----------------------------------------------------------------------------
+// Tokens
+// -----------------------------------------------------------------------------
+
+/// Tipo de evento de tecla decodificado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyKind {
+    Tap,
+    Press,
+    Repeat,
+    Release,
+}
+
+/// Texto producido por una tecla.
+///
+/// El caso común (un único carácter) no asigna memoria.
+#[derive(Debug, Clone, PartialEq)]
+enum KeyText {
+    Char(char),
+    String(String),
+}
+
+/// Unidad decodificada de la entrada.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Key { key: StandardKey, mods: u32, kind: KeyKind, text: Option<KeyText> },
+    Text(char),
+    Mouse { code: u32, x: u32, y: u32, pressed: bool },
+    Focus(bool),
+    PasteStart,
+    Ignore,
+}
+
+/// Resultado de analizar el inicio del buffer.
+enum Parse {
+    Incomplete,
+    Token(usize, Token),
+}
+
+
+// -----------------------------------------------------------------------------
+// Tablas
+// -----------------------------------------------------------------------------
+
+/// Tecla de una secuencia CSI / SS3 terminada en letra.
+fn letter_key(final_byte: u8) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match final_byte {
+        b'A' => ArrowUp,
+        b'B' => ArrowDown,
+        b'C' => ArrowRight,
+        b'D' => ArrowLeft,
+        b'H' => Home,
+        b'F' => End,
+        b'P' => F1,
+        b'Q' => F2,
+        b'R' => F3,
+        b'S' => F4,
+        b'E' => Numpad5,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Tecla de una secuencia `ESC [ n ~`.
+fn tilde_key(number: u32) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match number {
+        1 | 7 => Home,
+        2 => Insert,
+        3 => Delete,
+        4 | 8 => End,
+        5 => PageUp,
+        6 => PageDown,
+        11 => F1,
+        12 => F2,
+        13 => F3,
+        14 => F4,
+        15 => F5,
+        17 => F6,
+        18 => F7,
+        19 => F8,
+        20 => F9,
+        21 => F10,
+        23 => F11,
+        24 => F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Tecla de un código del protocolo de kitty (`ESC [ código u`).
+fn kitty_key(code: u32) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match code {
+        9 => Tab,
+        13 => Enter,
+        27 => Escape,
+        127 => Backspace,
+        57358 => CapsLock,
+        57359 => ScrollLock,
+        57360 => NumLock,
+        57361 => PrintScreen,
+        57362 => Pause,
+        57363 => ContextMenu,
+        57399 => Numpad0,
+        57400 => Numpad1,
+        57401 => Numpad2,
+        57402 => Numpad3,
+        57403 => Numpad4,
+        57404 => Numpad5,
+        57405 => Numpad6,
+        57406 => Numpad7,
+        57407 => Numpad8,
+        57408 => Numpad9,
+        57409 => NumpadDecimal,
+        57410 => NumpadDivide,
+        57411 => NumpadMultiply,
+        57412 => NumpadSubtract,
+        57413 => NumpadAdd,
+        57414 => NumpadEnter,
+        57441 => ShiftLeft,
+        57442 => ControlLeft,
+        57443 => AltLeft,
+        57444 => SuperLeft,
+        57447 => ShiftRight,
+        57448 => ControlRight,
+        57449 => AltRight,
+        57450 => SuperRight,
+        _ => return char::from_u32(code).and_then(StandardKey::from_us_char).map(|(key, _)| key),
+    };
+    Some(key)
+}
+
+/// Bit de modificador al que pertenece una tecla modificadora.
+fn modifier_bit(key: StandardKey) -> Option<u32> {
+    use StandardKey::*;
+
+    match key {
+        ShiftLeft | ShiftRight => Some(MOD_SHIFT),
+        AltLeft | AltRight => Some(MOD_ALT),
+        ControlLeft | ControlRight => Some(MOD_CTRL),
+        SuperLeft | SuperRight => Some(MOD_SUPER),
+        _ => None,
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Análisis de secuencias
+// -----------------------------------------------------------------------------
+
+/// Subcampo `sub` del campo `field` de los parámetros CSI (campos
+/// separados por `;`, subcampos por `:`), si existe y es numérico.
+fn field(params: &str, field: usize, sub: usize) -> Option<u32> {
+    params.split(';').nth(field)?.split(':').nth(sub)?.parse().ok()
+}
+
+/// Texto del tercer campo de una secuencia kitty (códigos separados por `:`).
+fn kitty_text(params: &str) -> Option<KeyText> {
+    let mut chars = params.split(';').nth(2)?.split(':').filter_map(|code| code.parse().ok().and_then(char::from_u32));
+    let first = chars.next()?;
+    match chars.next() {
+        None => Some(KeyText::Char(first)),
+        Some(second) => {
+            let mut text = String::from(first);
+            text.push(second);
+            text.extend(chars);
+            Some(KeyText::String(text))
+        }
+    }
+}
+
+/// Modificadores (ya sin el desplazamiento de 1) y tipo de evento del
+/// segundo campo CSI.
+fn modifiers_and_kind(params: &str, protocol: KeyboardProtocol) -> (u32, KeyKind) {
+    let mods = field(params, 1, 0).unwrap_or(1).saturating_sub(1);
+    let kind = match field(params, 1, 1) {
+        Some(2) => KeyKind::Repeat,
+        Some(3) => KeyKind::Release,
+        Some(_) => KeyKind::Press,
+        None if protocol == KeyboardProtocol::Kitty => KeyKind::Press,
+        None => KeyKind::Tap,
+    };
+    (mods, kind)
+}
+
+fn key_token(key: StandardKey, mods: u32, kind: KeyKind) -> Token {
+    Token::Key { key, mods, kind, text: None }
+}
+
+/// Analiza una secuencia completa `ESC [ params final`.
+fn parse_csi(params: &str, final_byte: u8, protocol: KeyboardProtocol) -> Token {
+    if let Some(mouse) = params.strip_prefix('<') {
+        return match (field(mouse, 0, 0), field(mouse, 1, 0), field(mouse, 2, 0), final_byte) {
+            (Some(code), Some(x), Some(y), b'M' | b'm') => Token::Mouse { code, x, y, pressed: final_byte == b'M' },
+            _ => Token::Ignore,
+        };
+    }
+
+    let (mods, kind) = modifiers_and_kind(params, protocol);
+    match final_byte {
+        b'I' if params.is_empty() => Token::Focus(true),
+        b'O' if params.is_empty() => Token::Focus(false),
+        b'Z' => key_token(StandardKey::Tab, mods | MOD_SHIFT, kind),
+        b'~' if field(params, 0, 0) == Some(200) => Token::PasteStart,
+        b'~' => match field(params, 0, 0).and_then(tilde_key) {
+            Some(key) => key_token(key, mods, kind),
+            None => Token::Ignore,
+        },
+        b'u' => match field(params, 0, 0).and_then(kitty_key) {
+            Some(key) => Token::Key { key, mods, kind, text: kitty_text(params) },
+            None => Token::Ignore,
+        },
+        other => match letter_key(other) {
+            Some(key) => key_token(key, mods, kind),
+            None => Token::Ignore,
+        },
+    }
+}
+
+/// Analiza la unidad al inicio de `buf`.
+fn parse_one(buf: &[u8], protocol: KeyboardProtocol) -> Parse {
+    let Some(&first) = buf.first() else { return Parse::Incomplete };
+    let tap = |key, mods| Parse::Token(1, key_token(key, mods, KeyKind::Tap));
+
+    match first {
+        0x1b => match buf.get(1) {
+            None => Parse::Incomplete,
+            Some(b'[') => {
+                for (i, &b) in buf.iter().enumerate().skip(2) {
+                    match b {
+                        0x20..=0x3f => {}
+                        0x40..=0x7e => {
+                            let params = String::from_utf8_lossy(&buf[2..i]);
+                            return Parse::Token(i + 1, parse_csi(&params, b, protocol));
+                        }
+                        _ => return Parse::Token(i, Token::Ignore),
+                    }
+                    if i >= MAX_CSI_LEN {
+                        return Parse::Token(i, Token::Ignore);
+                    }
+                }
+                Parse::Incomplete
+            }
+            Some(b'O') => match buf.get(2) {
+                None => Parse::Incomplete,
+                Some(&b) => match letter_key(b) {
+                    Some(key) => Parse::Token(3, key_token(key, 0, KeyKind::Tap)),
+                    None => Parse::Token(3, Token::Ignore),
+                },
+            },
+            // `ESC ESC [ ...`: Alt + secuencia; otro `ESC ESC` es un Escape.
+            Some(0x1b) => match buf.get(2) {
+                None => Parse::Incomplete,
+                Some(b'[' | b'O') => match parse_one(&buf[1..], protocol) {
+                    Parse::Incomplete => Parse::Incomplete,
+                    Parse::Token(len, Token::Key { key, mods, kind, .. }) => {
+                        Parse::Token(len + 1, key_token(key, mods | MOD_ALT, kind))
+                    }
+                    Parse::Token(..) => tap(StandardKey::Escape, 0),
+                },
+                Some(_) => tap(StandardKey::Escape, 0),
+            },
+            Some(_) => match parse_one(&buf[1..], protocol) {
+                Parse::Incomplete => Parse::Incomplete,
+                Parse::Token(len, Token::Key { key, mods, kind, .. }) => {
+                    Parse::Token(len + 1, key_token(key, mods | MOD_ALT, kind))
+                }
+                Parse::Token(len, token) => Parse::Token(len + 1, token),
+            },
+        },
+        0x00 => tap(StandardKey::Space, MOD_CTRL),
+        0x08 | 0x7f => tap(StandardKey::Backspace, 0),
+        b'\t' => tap(StandardKey::Tab, 0),
+        b'\r' | b'\n' => tap(StandardKey::Enter, 0),
+        0x01..=0x1a => match StandardKey::from_us_char((b'a' + first - 1) as char) {
+            Some((key, _)) => tap(key, MOD_CTRL),
+            None => Parse::Token(1, Token::Ignore),
+        },
+        0x1c => tap(StandardKey::Backslash, MOD_CTRL),
+        0x1d => tap(StandardKey::BracketRight, MOD_CTRL),
+        0x1e => tap(StandardKey::Digit6, MOD_CTRL),
+        0x1f => tap(StandardKey::Minus, MOD_CTRL),
+        0x20..=0x7e => {
+            let c = first as char;
+            match StandardKey::from_us_char(c) {
+                Some((key, shift)) => Parse::Token(
+                    1,
+                    Token::Key {
+                        key,
+                        mods: if shift { MOD_SHIFT } else { 0 },
+                        kind: KeyKind::Tap,
+                        text: Some(KeyText::Char(c)),
+                    },
+                ),
+                None => Parse::Token(1, Token::Text(c)),
+            }
+        }
+        _ => {
+            let len = match first {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Parse::Token(1, Token::Ignore),
+            };
+            if buf.len() < len {
+                return Parse::Incomplete;
+            }
+            match std::str::from_utf8(&buf[..len]).ok().and_then(|text| text.chars().next()) {
+                Some(c) => Parse::Token(len, Token::Text(c)),
+                None => Parse::Token(1, Token::Ignore),
+            }
+        }
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Decodificador
+// -----------------------------------------------------------------------------
+
+/// Decodificador puro de la entrada de un terminal.
+///
+/// Los terminales no informan marcas de tiempo: todos los eventos de una
+/// llamada a `feed` reciben el instante indicado.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalDecoder {
+    protocol: KeyboardProtocol,
+    pending: Vec<u8>,
+    held_modifiers: [Option<StandardKey>; 4],
+    mouse_position: Option<(u32, u32)>,
+    pasting: bool,
+    signals: Vec<RawSignal>,
+    text: Vec<TextInputEvent>,
+}
+
+impl TerminalDecoder {
+    /// Crea un decodificador en modo [`KeyboardProtocol::Legacy`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cambia el protocolo de teclado esperado.
+    pub fn with_protocol(mut self, protocol: KeyboardProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Protocolo de teclado esperado.
+    pub fn protocol(&self) -> KeyboardProtocol {
+        self.protocol
+    }
+
+    /// Indica si hay bytes retenidos (un `ESC` o una secuencia incompleta).
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Decodifica un trozo de bytes y devuelve los eventos de tecla y mouse.
+    ///
+    /// Las secuencias incompletas se retienen hasta la siguiente llamada.
+    pub fn feed(&mut self, bytes: &[u8], now: Timestamp) -> Vec<StandardKeyEvent> {
+        let mut out = Vec::new();
+        self.feed_into(bytes, now, &mut out);
+        out
+    }
+
+    /// Igual que `feed`, pero añade los eventos al final de `out`.
+    ///
+    /// Los eventos de tecla y mouse no asignan memoria una vez que los
+    /// buffers internos alcanzan su tamaño de trabajo; el texto sí.
+    pub fn feed_into(&mut self, bytes: &[u8], now: Timestamp, out: &mut Vec<StandardKeyEvent>) {
+        self.pending.extend_from_slice(bytes);
+
+        let mut offset = 0;
+        loop {
+            if self.pasting {
+                match self.consume_paste(offset) {
+                    Some(next) => offset = next,
+                    None => break,
+                }
+            }
+            match parse_one(&self.pending[offset..], self.protocol) {
+                Parse::Token(len, token) => {
+                    offset += len;
+                    self.apply(token, now, out);
+                }
+                Parse::Incomplete => break,
+            }
+        }
+        self.pending.drain(..offset);
+    }
+
+    /// Entrega como texto el contenido pegado desde `offset`.
+    ///
+    /// Retorna la posición tras `ESC [201~`, o `None` si el pegado sigue
+    /// abierto; en ese caso se retienen el posible inicio del terminador y
+    /// los caracteres UTF-8 incompletos, y se consume el resto.
+    fn consume_paste(&mut self, offset: usize) -> Option<usize> {
+        let rest = &self.pending[offset..];
+        if let Some(end) = rest.windows(PASTE_END.len()).position(|window| window == PASTE_END) {
+            let text = String::from_utf8_lossy(&rest[..end]).into_owned();
+            self.push_text(&text);
+            self.pasting = false;
+            return Some(offset + end + PASTE_END.len());
+        }
+
+        let keep = (1..PASTE_END.len()).rev().find(|&len| rest.ends_with(&PASTE_END[..len])).unwrap_or(0);
+        let safe = &rest[..rest.len() - keep];
+        let valid = match std::str::from_utf8(safe) {
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            _ => safe.len(),
+        };
+        let text = String::from_utf8_lossy(&safe[..valid]).into_owned();
+        self.push_text(&text);
+        self.pending.drain(offset..offset + valid);
+        None
+    }
+
+    /// Resuelve los bytes retenidos: cada `ESC` inicial se entrega como la
+    /// tecla `Escape`; el resto de la secuencia incompleta se descarta.
+    ///
+    /// Debe llamarse cuando pasa un tiempo prudencial sin nuevos bytes.
+    pub fn flush_pending(&mut self, now: Timestamp) -> Vec<StandardKeyEvent> {
+        let mut out = Vec::new();
+        self.flush_pending_into(now, &mut out);
+        out
+    }
+
+    /// Igual que `flush_pending`, pero añade los eventos al final de `out`.
+    ///
+    /// Durante un pegado entre corchetes no hace nada: los bytes retenidos
+    /// pueden ser el inicio del terminador.
+    pub fn flush_pending_into(&mut self, now: Timestamp, out: &mut Vec<StandardKeyEvent>) {
+        if self.pasting {
+            return;
+        }
+        let escapes = self.pending.iter().take_while(|&&b| b == 0x1b).count();
+        for _ in 0..escapes {
+            self.apply(key_token(StandardKey::Escape, 0, KeyKind::Tap), now, out);
+        }
+        self.pending.clear();
+    }
+
+    /// Devuelve y limpia las señales de foco decodificadas.
+    pub fn take_signals(&mut self) -> Vec<RawSignal> {
+        std::mem::take(&mut self.signals)
+    }
+
+    /// Devuelve y limpia el texto decodificado.
+    pub fn take_text(&mut self) -> Vec<TextInputEvent> {
+        std::mem::take(&mut self.text)
+    }
+
+    /// Descarta bytes retenidos, modificadores sintetizados y colas.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.held_modifiers = [None; 4];
+        self.mouse_position = None;
+        self.pasting = false;
+        self.signals.clear();
+        self.text.clear();
+    }
+
+    fn apply(&mut self, token: Token, now: Timestamp, out: &mut Vec<StandardKeyEvent>) {
+        match token {
+            Token::Key { key, mods, kind, text } => {
+                match text.filter(|_| kind != KeyKind::Release && mods & MOD_CHORD == 0) {
+                    Some(KeyText::Char(c)) => self.push_text(c.encode_utf8(&mut [0; 4])),
+                    Some(KeyText::String(text)) => self.push_text(&text),
+                    None => {}
+                }
+                self.apply_key(key, mods, kind, now, out);
+            }
+            Token::Text(c) => self.push_text(c.encode_utf8(&mut [0; 4])),
+            Token::Mouse { code, x, y, pressed } => self.apply_mouse(code, x, y, pressed, now, out),
+            Token::Focus(true) => self.signals.push(RawSignal::FocusGained),
+            Token::Focus(false) => self.signals.push(RawSignal::FocusLost),
+            Token::PasteStart => self.pasting = true,
+            Token::Ignore => {}
+        }
+    }
+
+    fn apply_key(&mut self, key: StandardKey, mods: u32, kind: KeyKind, now: Timestamp, out: &mut Vec<StandardKeyEvent>) {
+        // Teclas modificadoras reportadas por kitty: se registran tal cual.
+        if let Some(bit) = modifier_bit(key).filter(|_| kind != KeyKind::Tap) {
+            let slot = MODIFIERS.iter().position(|(b, _)| *b == bit).unwrap_or(0);
+            self.held_modifiers[slot] = (kind != KeyKind::Release).then_some(key);
+            out.push(Self::key_event(key, kind != KeyKind::Release, kind == KeyKind::Repeat, now));
+            return;
+        }
+
+        self.sync_modifiers(mods, now, out);
+        match kind {
+            KeyKind::Tap => {
+                out.push(Self::key_event(key, true, false, now));
+                out.push(Self::key_event(key, false, false, now));
+                self.sync_modifiers(0, now, out);
+            }
+            KeyKind::Press => out.push(Self::key_event(key, true, false, now)),
+            KeyKind::Repeat => out.push(Self::key_event(key, true, true, now)),
+            KeyKind::Release => out.push(Self::key_event(key, false, false, now)),
+        }
+    }
+
+    /// Pulsa o suelta modificadores sintetizados hasta coincidir con `mods`.
+    fn sync_modifiers(&mut self, mods: u32, now: Timestamp, out: &mut Vec<StandardKeyEvent>) {
+        for (slot, (bit, key)) in MODIFIERS.iter().enumerate() {
+            let wanted = mods & bit != 0;
+            match (wanted, self.held_modifiers[slot]) {
+                (true, None) => {
+                    self.held_modifiers[slot] = Some(*key);
+                    out.push(Self::key_event(*key, true, false, now));
+                }
+                (false, Some(held)) => {
+                    self.held_modifiers[slot] = None;
+                    out.push(Self::key_event(held, false, false, now));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn apply_mouse(&mut self, code: u32, x: u32, y: u32, pressed: bool, now: Timestamp, out: &mut Vec<StandardKeyEvent>) {
+        if let Some((last_x, last_y)) = self.mouse_position.replace((x, y)) {
+            let (dx, dy) = (x as f32 - last_x as f32, y as f32 - last_y as f32);
+            if dx != 0.0 {
+                out.push(Self::axis_event(StandardKey::MouseMotionX, dx, now));
+            }
+            if dy != 0.0 {
+                out.push(Self::axis_event(StandardKey::MouseMotionY, dy, now));
+            }
+        }
+
+        let button = code & 0b11;
+        if code & 64 != 0 && code & 128 == 0 {
+            if pressed {
+                let (axis, value) = match button {
+                    0 => (StandardKey::MouseWheel, 1.0),
+                    1 => (StandardKey::MouseWheel, -1.0),
+                    2 => (StandardKey::MouseWheelHorizontal, -1.0),
+                    _ => (StandardKey::MouseWheelHorizontal, 1.0),
+                };
+                out.push(Self::axis_event(axis, value, now));
+            }
+            return;
+        }
+        if code & 32 != 0 {
+            return;
+        }
+
+        let key = match (code & 128 != 0, button) {
+            (false, 0) => StandardKey::MouseLeft,
+            (false, 1) => StandardKey::MouseMiddle,
+            (false, 2) => StandardKey::MouseRight,
+            (true, 0) => StandardKey::MouseBack,
+            (true, 1) => StandardKey::MouseForward,
+            _ => return,
+        };
+        let state = if pressed { StandardKeyState::Pressed } else { StandardKeyState::Released };
+        out.push(StandardKeyEvent {
+            key,
+            state,
+            timestamp: now,
+            device: StandardDevice::Mouse,
+            repeat: false,
+            value: None,
+        });
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.text.last_mut() {
+            Some(TextInputEvent::Commit(last)) => last.push_str(text),
+            _ => self.text.push(TextInputEvent::Commit(text.to_owned())),
+        }
+    }
+
+    fn key_event(key: StandardKey, pressed: bool, repeat: bool, timestamp: Timestamp) -> StandardKeyEvent {
+        StandardKeyEvent {
+            key,
+            state: if pressed { StandardKeyState::Pressed } else { StandardKeyState::Released },
+            timestamp,
+            device: StandardDevice::Keyboard,
+            repeat,
+            value: None,
+        }
+    }
+
+    fn axis_event(key: StandardKey, value: f32, timestamp: Timestamp) -> StandardKeyEvent {
+        StandardKeyEvent {
+            key,
+            state: StandardKeyState::Changed,
+            timestamp,
+            device: StandardDevice::Mouse,
+            repeat: false,
+            value: Some(value),
+        }
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// RawInputLayer
+// -----------------------------------------------------------------------------
+
+/// Backend crudo de terminal sobre cualquier lector **no bloqueante**
+/// (normalmente la entrada estándar en modo raw).
+///
+/// Un `ESC` retenido se resuelve como tecla `Escape` en el primer frame en
+/// que no llegan bytes nuevos. Los errores de E/S detienen la lectura del
+/// frame y quedan disponibles en `take_error`.
+#[derive(Debug)]
+pub struct TerminalRawLayer<R> {
+    reader: R,
+    decoder: TerminalDecoder,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> TerminalRawLayer<R> {
+    /// Crea el backend sobre un lector ya configurado.
+    pub fn new(reader: R) -> Self {
+        Self::with_decoder(reader, TerminalDecoder::new())
+    }
+
+    /// Igual que `new`, con un decodificador configurado.
+    pub fn with_decoder(reader: R, decoder: TerminalDecoder) -> Self {
+        Self { reader, decoder, error: None }
+    }
+
+    /// Acceso mutable al decodificador.
+    pub fn decoder_mut(&mut self) -> &mut TerminalDecoder {
+        &mut self.decoder
+    }
+
+    /// Devuelve y limpia el último error de E/S.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+impl<R: Read> RawInputLayer for TerminalRawLayer<R> {
+    type KeyEvent = StandardKeyEvent;
+
+    fn poll_events(&mut self) -> Vec<Self::KeyEvent> {
+        let mut events = Vec::new();
+        self.poll_events_into(&mut events);
+        events
+    }
+
+    fn poll_events_into(&mut self, out: &mut Vec<Self::KeyEvent>) {
+        let mut buffer = [0u8; 1024];
+        let mut received = false;
+        loop {
+            match self.reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => {
+                    received = true;
+                    self.decoder.feed_into(&buffer[..read], Timestamp::now(), out);
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.error = Some(err);
+                    break;
+                }
+            }
+        }
+        if !received && self.decoder.has_pending() {
+            self.decoder.flush_pending_into(Timestamp::now(), out);
+        }
+    }
+
+    fn poll_signals(&mut self) -> Vec<RawSignal> {
+        self.decoder.take_signals()
+    }
+
+    fn poll_text(&mut self) -> Vec<TextInputEvent> {
+        self.decoder.take_text()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use StandardKey::*;
+    use StandardKeyState::{Changed, Pressed, Released};
+
+    fn decode(protocol: KeyboardProtocol, bytes: &[u8]) -> (TerminalDecoder, Vec<(StandardKey, StandardKeyState)>) {
+        let mut decoder = TerminalDecoder::new().with_protocol(protocol);
+        let events = decoder.feed(bytes, Timestamp::ZERO).iter().map(|e| (e.key, e.state)).collect();
+        (decoder, events)
+    }
+
+    fn commit(text: &str) -> Vec<TextInputEvent> {
+        vec![TextInputEvent::Commit(text.to_owned())]
+    }
+
+    #[test]
+    fn csi_arrow_with_modifiers() {
+        let (_, events) = decode(KeyboardProtocol::Legacy, b"\x1b[1;5A\x1b[1;4D");
+        assert_eq!(
+            events,
+            [
+                (ControlLeft, Pressed),
+                (ArrowUp, Pressed),
+                (ArrowUp, Released),
+                (ControlLeft, Released),
+                (ShiftLeft, Pressed),
+                (AltLeft, Pressed),
+                (ArrowLeft, Pressed),
+                (ArrowLeft, Released),
+                (ShiftLeft, Released),
+                (AltLeft, Released),
+            ]
+        );
+    }
+
+    #[test]
+    fn csi_z_is_shift_tab() {
+        let (_, events) = decode(KeyboardProtocol::Legacy, b"\x1b[Z");
+        assert_eq!(events, [(ShiftLeft, Pressed), (Tab, Pressed), (Tab, Released), (ShiftLeft, Released)]);
+    }
+
+    #[test]
+    fn ss3_function_key() {
+        let (_, events) = decode(KeyboardProtocol::Legacy, b"\x1bOP");
+        assert_eq!(events, [(F1, Pressed), (F1, Released)]);
+    }
+
+    #[test]
+    fn sgr_press_drag_release() {
+        let mut decoder = TerminalDecoder::new();
+        let events = decoder.feed(b"\x1b[<0;10;5M\x1b[<32;12;4M\x1b[<0;12;4m", Timestamp::ZERO);
+        let decoded: Vec<_> = events.iter().map(|e| (e.key, e.state, e.value, e.device)).collect();
+        assert_eq!(
+            decoded,
+            [
+                (MouseLeft, Pressed, None, StandardDevice::Mouse),
+                (MouseMotionX, Changed, Some(2.0), StandardDevice::Mouse),
+                (MouseMotionY, Changed, Some(-1.0), StandardDevice::Mouse),
+                (MouseLeft, Released, None, StandardDevice::Mouse),
+            ]
+        );
+    }
+
+    #[test]
+    fn sgr_wheel() {
+        let mut decoder = TerminalDecoder::new();
+        let events = decoder.feed(b"\x1b[<64;1;1M\x1b[<65;1;1M", Timestamp::ZERO);
+        let decoded: Vec<_> = events.iter().map(|e| (e.key, e.value)).collect();
+        assert_eq!(decoded, [(MouseWheel, Some(1.0)), (MouseWheel, Some(-1.0))]);
+    }
+
+    #[test]
+    fn kitty_event_types() {
+        let (_, events) = decode(KeyboardProtocol::Kitty, b"\x1b[97u\x1b[97;1:2u\x1b[97;1:3u");
+        assert_eq!(events, [(A, Pressed), (A, Pressed), (A, Released)]);
+    }
+
+    #[test]
+    fn kitty_lock_bits_keep_text() {
+        // Num Lock (128) y Caps Lock + Shift (64 + 1): ambos producen texto.
+        let (mut decoder, events) = decode(KeyboardProtocol::Kitty, b"\x1b[97;129;97u\x1b[97;66;65u");
+        assert_eq!(events, [(A, Pressed), (ShiftLeft, Pressed), (A, Pressed)]);
+        assert_eq!(decoder.take_text(), commit("aA"));
+    }
+
+    #[test]
+    fn kitty_chord_suppresses_text() {
+        // Ctrl + Num Lock.
+        let (mut decoder, events) = decode(KeyboardProtocol::Kitty, b"\x1b[97;133;97u");
+        assert_eq!(events, [(ControlLeft, Pressed), (A, Pressed)]);
+        assert!(decoder.take_text().is_empty());
+    }
+
+    #[test]
+    fn kitty_modifier_keys_are_reported_as_is() {
+        let (_, events) = decode(KeyboardProtocol::Kitty, b"\x1b[57447;2u\x1b[57447;1:3u");
+        assert_eq!(events, [(ShiftRight, Pressed), (ShiftRight, Released)]);
+    }
+
+    #[test]
+    fn bracketed_paste_is_text_only() {
+        let (mut decoder, events) = decode(KeyboardProtocol::Legacy, b"\x1b[200~hola \x1b[A\xc3\xb1\x1b[201~x");
+        assert_eq!(events, [(X, Pressed), (X, Released)]);
+        assert_eq!(decoder.take_text(), commit("hola \x1b[Añx"));
+    }
+
+    #[test]
+    fn bracketed_paste_split_across_reads() {
+        let mut decoder = TerminalDecoder::new();
+        let mut events = decoder.feed(b"\x1b[200~ab\xc3", Timestamp::ZERO);
+        events.extend(decoder.flush_pending(Timestamp::ZERO));
+        events.extend(decoder.feed(b"\xb1\x1b[20", Timestamp::ZERO));
+        events.extend(decoder.feed(b"1~", Timestamp::ZERO));
+        assert!(events.is_empty());
+        assert!(!decoder.has_pending());
+        assert_eq!(decoder.take_text(), commit("abñ"));
+    }
+
+    #[test]
+    fn escape_escape_sequence_is_alt() {
+        let (_, events) = decode(KeyboardProtocol::Legacy, b"\x1b\x1b[A");
+        assert_eq!(events, [(AltLeft, Pressed), (ArrowUp, Pressed), (ArrowUp, Released), (AltLeft, Released)]);
+    }
+
+    #[test]
+    fn escape_escape_letter_is_escape_then_alt() {
+        let (_, events) = decode(KeyboardProtocol::Legacy, b"\x1b\x1ba");
+        assert_eq!(
+            events,
+            [(Escape, Pressed), (Escape, Released), (AltLeft, Pressed), (A, Pressed), (A, Released), (AltLeft, Released)]
+        );
+    }
+
+    #[test]
+    fn lone_escapes_wait_for_flush() {
+        let (mut decoder, events) = decode(KeyboardProtocol::Legacy, b"\x1b\x1b");
+        assert!(events.is_empty());
+        assert!(decoder.has_pending());
+        let flushed: Vec<_> = decoder.flush_pending(Timestamp::ZERO).iter().map(|e| (e.key, e.state)).collect();
+        assert_eq!(flushed, [(Escape, Pressed), (Escape, Released), (Escape, Pressed), (Escape, Released)]);
+    }
+
+    #[test]
+    fn split_csi_sequence() {
+        let mut decoder = TerminalDecoder::new();
+        assert!(decoder.feed(b"\x1b[1;", Timestamp::ZERO).is_empty());
+        let events: Vec<_> = decoder.feed(b"2B", Timestamp::ZERO).iter().map(|e| (e.key, e.state)).collect();
+        assert_eq!(events, [(ShiftLeft, Pressed), (ArrowDown, Pressed), (ArrowDown, Released), (ShiftLeft, Released)]);
+    }
+
+    #[test]
+    fn printable_text_and_ctrl() {
+        let (mut decoder, events) = decode(KeyboardProtocol::Legacy, "B\x03ñ".as_bytes());
+        assert_eq!(
+            events,
+            [
+                (ShiftLeft, Pressed),
+                (B, Pressed),
+                (B, Released),
+                (ShiftLeft, Released),
+                (ControlLeft, Pressed),
+                (C, Pressed),
+                (C, Released),
+                (ControlLeft, Released),
+            ]
+        );
+        assert_eq!(decoder.take_text(), commit("Bñ"));
+    }
+
+    #[test]
+    fn focus_reports_are_signals() {
+        let (mut decoder, events) = decode(KeyboardProtocol::Legacy, b"\x1b[O\x1b[I");
+        assert!(events.is_empty());
+        assert_eq!(decoder.take_signals(), [RawSignal::FocusLost, RawSignal::FocusGained]);
+    }
+}
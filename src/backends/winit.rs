@@ -0,0 +1,625 @@
+//! ---------------------------------------------------------------------------
+//! Adaptador crudo para **winit** (0.30).
+//!
+//! Convierte `WindowEvent` y `DeviceEvent` en eventos crudos del crate:
+//!
+//! - `KeyboardInput` → tecla física (`PhysicalKey`) y lógica (`Key`), con
+//!   auto-repetición y texto asociado,
+//! - `MouseInput` → botones del mouse,
+//! - `CursorMoved` o `DeviceEvent::MouseMotion` → movimiento (delta),
+//! - `MouseWheel` → ruedas vertical y horizontal (en líneas),
+//! - `Focused` → [`RawSignal::FocusLost`] / [`RawSignal::FocusGained`],
+//! - `Ime` → [`TextInputEvent`].
+//!
+//! Los códigos se construyen con [`KeyCodeExt::from_backend`] a partir de un
+//! [`WinitKey`], por lo que el adaptador funciona con cualquier tipo de código
+//! del motor cuyo `BackendKey` sea `WinitKey` (el propio `WinitKey` incluido).
+//!
+//! El adaptador no necesita ventana: basta con reenviarle los eventos del
+//! `ApplicationHandler`. Como `winit::event::KeyEvent` no puede construirse
+//! fuera de winit, las teclas también pueden inyectarse con
+//! [`WinitRawLayer::handle_key`].
+//!
+//! ```ignore
+//! fn window_event(&mut self, _: &ActiveEventLoop, _: WindowId, event: WindowEvent) {
+//!     self.raw.handle_window_event(&event);
+//! }
+//! ```
+//! ---------------------------------------------------------------------------
+
+use std::marker::PhantomData;
+
+use winit::{
+    event::{DeviceEvent, ElementState, Ime, MouseButton, MouseScrollDelta, WindowEvent},
+    keyboard::{Key, KeyCode, NamedKey, PhysicalKey},
+};
+
+use crate::layers::{
+    raw_layer::{KeyCodeExt, KeyEventExt, KeyEventRestampExt, RawInputLayer, RawSignal, TextInputEvent},
+    standard_keys::{StandardDevice, StandardKey, StandardKeyState, ToStandardKey},
+    timestamp::Timestamp,
+};
+
+
+/// Píxeles por línea de rueda por defecto, para `MouseScrollDelta::PixelDelta`.
+pub const DEFAULT_PIXELS_PER_LINE: f32 = 40.0;
+
+
+// -----------------------------------------------------------------------------
+// Códigos
+// -----------------------------------------------------------------------------
+
+/// Código de entrada tal como lo describe winit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WinitKey {
+    /// Tecla física (posición en el teclado).
+    Physical(PhysicalKey),
+    /// Tecla lógica (significado según la distribución activa).
+    Logical(Key),
+    /// Botón del mouse.
+    Mouse(MouseButton),
+    /// Movimiento horizontal del mouse.
+    MouseMotionX,
+    /// Movimiento vertical del mouse.
+    MouseMotionY,
+    /// Rueda vertical.
+    MouseWheel,
+    /// Rueda horizontal.
+    MouseWheelHorizontal,
+}
+
+/// `WinitKey` puede usarse directamente como código de tecla.
+impl KeyCodeExt for WinitKey {
+    type NativeKey = WinitKey;
+    type BackendKey = WinitKey;
+
+    fn from_native(key: &Self::NativeKey) -> Self {
+        key.clone()
+    }
+
+    fn from_backend(key: &Self::BackendKey) -> Self {
+        key.clone()
+    }
+
+    fn to_native(&self) -> &Self::NativeKey {
+        self
+    }
+
+    fn to_backend(&self) -> Self::BackendKey {
+        self.clone()
+    }
+}
+
+impl ToStandardKey for WinitKey {
+    fn to_standard_key(&self) -> Option<StandardKey> {
+        match self {
+            WinitKey::Physical(PhysicalKey::Code(code)) => standard_key_for_code(*code),
+            WinitKey::Physical(PhysicalKey::Unidentified(_)) => None,
+            WinitKey::Logical(Key::Named(named)) => standard_key_for_named(*named),
+            WinitKey::Logical(Key::Character(text)) => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => StandardKey::from_us_char(c.to_ascii_lowercase()).map(|(key, _)| key),
+                    _ => None,
+                }
+            }
+            WinitKey::Logical(_) => None,
+            WinitKey::Mouse(button) => match button {
+                MouseButton::Left => Some(StandardKey::MouseLeft),
+                MouseButton::Right => Some(StandardKey::MouseRight),
+                MouseButton::Middle => Some(StandardKey::MouseMiddle),
+                MouseButton::Back => Some(StandardKey::MouseBack),
+                MouseButton::Forward => Some(StandardKey::MouseForward),
+                MouseButton::Other(_) => None,
+            },
+            WinitKey::MouseMotionX => Some(StandardKey::MouseMotionX),
+            WinitKey::MouseMotionY => Some(StandardKey::MouseMotionY),
+            WinitKey::MouseWheel => Some(StandardKey::MouseWheel),
+            WinitKey::MouseWheelHorizontal => Some(StandardKey::MouseWheelHorizontal),
+        }
+    }
+}
+
+/// Genera la tabla `KeyCode` → `StandardKey` para variantes homónimas.
+macro_rules! same_name_codes {
+    ($code:expr; $( $winit:ident => $standard:ident ),* ; $( $name:ident ),* $(,)?) => {
+        match $code {
+            $( KeyCode::$winit => Some(StandardKey::$standard), )*
+            $( KeyCode::$name => Some(StandardKey::$name), )*
+            _ => None,
+        }
+    };
+}
+
+/// Tecla estándar de un código físico de winit.
+pub fn standard_key_for_code(code: KeyCode) -> Option<StandardKey> {
+    same_name_codes!(code;
+        KeyA => A, KeyB => B, KeyC => C, KeyD => D, KeyE => E, KeyF => F, KeyG => G,
+        KeyH => H, KeyI => I, KeyJ => J, KeyK => K, KeyL => L, KeyM => M, KeyN => N,
+        KeyO => O, KeyP => P, KeyQ => Q, KeyR => R, KeyS => S, KeyT => T, KeyU => U,
+        KeyV => V, KeyW => W, KeyX => X, KeyY => Y, KeyZ => Z;
+        Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+        Escape, Tab, CapsLock, Space, Enter, Backspace,
+        ShiftLeft, ShiftRight, ControlLeft, ControlRight, AltLeft, AltRight,
+        SuperLeft, SuperRight, ContextMenu,
+        ArrowUp, ArrowDown, ArrowLeft, ArrowRight,
+        Insert, Delete, Home, End, PageUp, PageDown,
+        PrintScreen, ScrollLock, Pause,
+        Backquote, Minus, Equal, BracketLeft, BracketRight, Backslash,
+        Semicolon, Quote, Comma, Period, Slash,
+        NumLock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6,
+        Numpad7, Numpad8, Numpad9, NumpadAdd, NumpadSubtract, NumpadMultiply,
+        NumpadDivide, NumpadDecimal, NumpadEnter,
+    )
+}
+
+/// Tecla estándar de una tecla lógica con nombre.
+///
+/// Las teclas lógicas no distinguen lado: los modificadores se asignan a la
+/// variante izquierda (salvo `AltGraph`, que corresponde a `AltRight`).
+pub fn standard_key_for_named(named: NamedKey) -> Option<StandardKey> {
+    use StandardKey::*;
+
+    let key = match named {
+        NamedKey::Shift => ShiftLeft,
+        NamedKey::Control => ControlLeft,
+        NamedKey::Alt => AltLeft,
+        NamedKey::AltGraph => AltRight,
+        NamedKey::Super => SuperLeft,
+        NamedKey::CapsLock => CapsLock,
+        NamedKey::NumLock => NumLock,
+        NamedKey::ScrollLock => ScrollLock,
+        NamedKey::Enter => Enter,
+        NamedKey::Tab => Tab,
+        NamedKey::Space => Space,
+        NamedKey::Escape => Escape,
+        NamedKey::Backspace => Backspace,
+        NamedKey::Delete => Delete,
+        NamedKey::Insert => Insert,
+        NamedKey::Home => Home,
+        NamedKey::End => End,
+        NamedKey::PageUp => PageUp,
+        NamedKey::PageDown => PageDown,
+        NamedKey::ArrowUp => ArrowUp,
+        NamedKey::ArrowDown => ArrowDown,
+        NamedKey::ArrowLeft => ArrowLeft,
+        NamedKey::ArrowRight => ArrowRight,
+        NamedKey::ContextMenu => ContextMenu,
+        NamedKey::PrintScreen => PrintScreen,
+        NamedKey::Pause => Pause,
+        NamedKey::F1 => F1,
+        NamedKey::F2 => F2,
+        NamedKey::F3 => F3,
+        NamedKey::F4 => F4,
+        NamedKey::F5 => F5,
+        NamedKey::F6 => F6,
+        NamedKey::F7 => F7,
+        NamedKey::F8 => F8,
+        NamedKey::F9 => F9,
+        NamedKey::F10 => F10,
+        NamedKey::F11 => F11,
+        NamedKey::F12 => F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
+
+// -----------------------------------------------------------------------------
+// Eventos
+// -----------------------------------------------------------------------------
+
+/// Evento crudo producido por [`WinitRawLayer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WinitKeyEvent<K = WinitKey> {
+    /// Código físico (o del botón / eje).
+    pub physical: K,
+    /// Código lógico, solo para teclas de teclado.
+    pub logical: Option<K>,
+    /// Transición.
+    pub state: StandardKeyState,
+    /// Instante en que el adaptador recibió el evento.
+    pub timestamp: Timestamp,
+    /// Dispositivo de origen.
+    pub device: StandardDevice,
+    /// `true` si es una auto-repetición del sistema.
+    pub repeat: bool,
+    /// Valor analógico, solo para ejes.
+    pub value: Option<f32>,
+}
+
+impl<K: KeyCodeExt + Clone> KeyEventExt for WinitKeyEvent<K> {
+    type KeyCode = K;
+    type KeyState = StandardKeyState;
+    type DeviceKind = StandardDevice;
+
+    fn keycode(&self) -> Self::KeyCode {
+        self.physical.clone()
+    }
+
+    fn physical_key(&self) -> Self::KeyCode {
+        self.physical.clone()
+    }
+
+    fn logical_key(&self) -> Self::KeyCode {
+        self.logical.clone().unwrap_or_else(|| self.physical.clone())
+    }
+
+    fn state(&self) -> Self::KeyState {
+        self.state
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    fn device_kind(&self) -> Self::DeviceKind {
+        self.device
+    }
+
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+
+    fn axis_value(&self) -> Option<f32> {
+        self.value
+    }
+}
+
+impl<K: KeyCodeExt + Clone> KeyEventRestampExt for WinitKeyEvent<K> {
+    fn restamp(&mut self, timestamp: Timestamp) {
+        self.timestamp = timestamp;
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Adaptador
+// -----------------------------------------------------------------------------
+
+/// Fuente del movimiento del mouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MotionSource {
+    /// Diferencia entre posiciones del cursor (`WindowEvent::CursorMoved`).
+    /// Se detiene en los bordes de la ventana.
+    #[default]
+    Cursor,
+    /// Movimiento crudo del dispositivo (`DeviceEvent::MouseMotion`).
+    /// Adecuado con el cursor capturado (cámaras en primera persona).
+    Device,
+}
+
+/// Adaptador de winit que acumula eventos hasta el siguiente `poll_*`.
+#[derive(Debug, Clone)]
+pub struct WinitRawLayer<K = WinitKey> {
+    events: Vec<WinitKeyEvent<K>>,
+    signals: Vec<RawSignal>,
+    text: Vec<TextInputEvent>,
+    motion_source: MotionSource,
+    pixels_per_line: f32,
+    cursor: Option<(f64, f64)>,
+    _key: PhantomData<K>,
+}
+
+impl<K: KeyCodeExt<BackendKey = WinitKey>> Default for WinitRawLayer<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: KeyCodeExt<BackendKey = WinitKey>> WinitRawLayer<K> {
+    /// Crea el adaptador con movimiento por cursor.
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            signals: Vec::new(),
+            text: Vec::new(),
+            motion_source: MotionSource::default(),
+            pixels_per_line: DEFAULT_PIXELS_PER_LINE,
+            cursor: None,
+            _key: PhantomData,
+        }
+    }
+
+    /// Cambia la fuente del movimiento del mouse.
+    pub fn with_motion_source(mut self, source: MotionSource) -> Self {
+        self.motion_source = source;
+        self
+    }
+
+    /// Cambia la conversión de desplazamientos de rueda en píxeles a líneas.
+    ///
+    /// Los valores no positivos o no finitos se ignoran y se conserva
+    /// [`DEFAULT_PIXELS_PER_LINE`].
+    pub fn with_pixels_per_line(mut self, pixels: f32) -> Self {
+        self.pixels_per_line = if pixels.is_finite() && pixels > 0.0 { pixels } else { DEFAULT_PIXELS_PER_LINE };
+        self
+    }
+
+    /// Procesa un evento de ventana. Devuelve `true` si el adaptador lo usó.
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::KeyboardInput { event, .. } => {
+                self.handle_key(
+                    event.physical_key,
+                    &event.logical_key,
+                    event.state,
+                    event.repeat,
+                    event.text.as_deref(),
+                );
+            }
+            WindowEvent::MouseInput { state, button, .. } => self.handle_mouse_button(*button, *state),
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = (position.x, position.y);
+                let previous = self.cursor.replace(position);
+                if let (MotionSource::Cursor, Some(previous)) = (self.motion_source, previous) {
+                    self.handle_motion(position.0 - previous.0, position.1 - previous.1);
+                }
+            }
+            WindowEvent::CursorLeft { .. } => self.cursor = None,
+            WindowEvent::MouseWheel { delta, .. } => self.handle_wheel(*delta),
+            WindowEvent::Focused(focused) => {
+                self.cursor = None;
+                self.signals.push(if *focused { RawSignal::FocusGained } else { RawSignal::FocusLost });
+            }
+            WindowEvent::Ime(ime) => match ime {
+                Ime::Commit(text) => self.push_text(text),
+                Ime::Preedit(text, _) if text.is_empty() => self.text.push(TextInputEvent::PreeditCleared),
+                Ime::Preedit(text, cursor) => self.text.push(TextInputEvent::Preedit {
+                    text: text.clone(),
+                    cursor: cursor.map(|(start, end)| start..end),
+                }),
+                Ime::Disabled => self.text.push(TextInputEvent::PreeditCleared),
+                Ime::Enabled => {}
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Procesa un evento de dispositivo. Devuelve `true` si el adaptador lo usó.
+    ///
+    /// Solo se usa el movimiento crudo, y solo con [`MotionSource::Device`];
+    /// teclas y botones ya llegan como eventos de ventana.
+    pub fn handle_device_event(&mut self, event: &DeviceEvent) -> bool {
+        match event {
+            DeviceEvent::MouseMotion { delta } if self.motion_source == MotionSource::Device => {
+                self.handle_motion(delta.0, delta.1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Registra una tecla de teclado. `text` es el texto producido por la
+    /// pulsación, si lo hay (los caracteres de control se ignoran).
+    pub fn handle_key(
+        &mut self,
+        physical: PhysicalKey,
+        logical: &Key,
+        state: ElementState,
+        repeat: bool,
+        text: Option<&str>,
+    ) {
+        let pressed = state == ElementState::Pressed;
+        if let Some(text) = text.filter(|_| pressed) {
+            let printable: String = text.chars().filter(|c| !c.is_control()).collect();
+            if !printable.is_empty() {
+                self.push_text(&printable);
+            }
+        }
+        self.events.push(WinitKeyEvent {
+            physical: K::from_backend(&WinitKey::Physical(physical)),
+            logical: Some(K::from_backend(&WinitKey::Logical(logical.clone()))),
+            state: if pressed { StandardKeyState::Pressed } else { StandardKeyState::Released },
+            timestamp: Timestamp::now(),
+            device: StandardDevice::Keyboard,
+            repeat,
+            value: None,
+        });
+    }
+
+    /// Registra un botón del mouse.
+    pub fn handle_mouse_button(&mut self, button: MouseButton, state: ElementState) {
+        let state = match state {
+            ElementState::Pressed => StandardKeyState::Pressed,
+            ElementState::Released => StandardKeyState::Released,
+        };
+        self.push_mouse(WinitKey::Mouse(button), state, None);
+    }
+
+    /// Registra un movimiento relativo del mouse (píxeles).
+    pub fn handle_motion(&mut self, dx: f64, dy: f64) {
+        if dx != 0.0 {
+            self.push_mouse(WinitKey::MouseMotionX, StandardKeyState::Changed, Some(dx as f32));
+        }
+        if dy != 0.0 {
+            self.push_mouse(WinitKey::MouseMotionY, StandardKeyState::Changed, Some(dy as f32));
+        }
+    }
+
+    /// Registra un desplazamiento de rueda, convertido a líneas.
+    pub fn handle_wheel(&mut self, delta: MouseScrollDelta) {
+        let (x, y) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => (x, y),
+            MouseScrollDelta::PixelDelta(position) => (
+                position.x as f32 / self.pixels_per_line,
+                position.y as f32 / self.pixels_per_line,
+            ),
+        };
+        if y != 0.0 {
+            self.push_mouse(WinitKey::MouseWheel, StandardKeyState::Changed, Some(y));
+        }
+        if x != 0.0 {
+            self.push_mouse(WinitKey::MouseWheelHorizontal, StandardKeyState::Changed, Some(x));
+        }
+    }
+
+    /// Registra la suspensión de la aplicación (`ApplicationHandler::suspended`).
+    pub fn handle_suspended(&mut self) {
+        self.cursor = None;
+        self.signals.push(RawSignal::Suspended);
+    }
+
+    /// Registra la reanudación de la aplicación (`ApplicationHandler::resumed`).
+    pub fn handle_resumed(&mut self) {
+        self.signals.push(RawSignal::Resumed);
+    }
+
+    fn push_mouse(&mut self, key: WinitKey, state: StandardKeyState, value: Option<f32>) {
+        self.events.push(WinitKeyEvent {
+            physical: K::from_backend(&key),
+            logical: None,
+            state,
+            timestamp: Timestamp::now(),
+            device: StandardDevice::Mouse,
+            repeat: false,
+            value,
+        });
+    }
+
+    fn push_text(&mut self, text: &str) {
+        match self.text.last_mut() {
+            Some(TextInputEvent::Commit(last)) => last.push_str(text),
+            _ => self.text.push(TextInputEvent::Commit(text.to_owned())),
+        }
+    }
+}
+
+impl<K: KeyCodeExt<BackendKey = WinitKey> + Clone> RawInputLayer for WinitRawLayer<K> {
+    type KeyEvent = WinitKeyEvent<K>;
+
+    fn poll_events(&mut self) -> Vec<Self::KeyEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn poll_events_into(&mut self, out: &mut Vec<Self::KeyEvent>) {
+        out.append(&mut self.events);
+    }
+
+    fn poll_signals(&mut self) -> Vec<RawSignal> {
+        std::mem::take(&mut self.signals)
+    }
+
+    fn poll_text(&mut self) -> Vec<TextInputEvent> {
+        std::mem::take(&mut self.text)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use winit::{dpi::PhysicalPosition, event::DeviceId, keyboard::SmolStr};
+
+    fn layer() -> WinitRawLayer {
+        WinitRawLayer::new()
+    }
+
+    fn wheel(layer: &mut WinitRawLayer) -> Vec<(WinitKey, Option<f32>)> {
+        layer.poll_events().into_iter().map(|e| (e.physical, e.value)).collect()
+    }
+
+    #[test]
+    fn key_keeps_physical_and_logical_codes() {
+        let mut layer = layer();
+        // Distribución AZERTY: la tecla física Q produce una `a`.
+        let logical = Key::Character(SmolStr::new("a"));
+        layer.handle_key(PhysicalKey::Code(KeyCode::KeyQ), &logical, ElementState::Pressed, false, Some("a"));
+
+        let event = &layer.poll_events()[0];
+        assert_eq!(event.physical_key(), WinitKey::Physical(PhysicalKey::Code(KeyCode::KeyQ)));
+        assert_eq!(event.logical_key(), WinitKey::Logical(logical));
+        assert_eq!(event.physical_key().to_standard_key(), Some(StandardKey::Q));
+        assert_eq!(event.logical_key().to_standard_key(), Some(StandardKey::A));
+        assert_eq!(event.device_kind(), StandardDevice::Keyboard);
+    }
+
+    #[test]
+    fn named_and_uppercase_logical_keys_map_to_standard() {
+        assert_eq!(WinitKey::Logical(Key::Named(NamedKey::Enter)).to_standard_key(), Some(StandardKey::Enter));
+        assert_eq!(WinitKey::Logical(Key::Character(SmolStr::new("W"))).to_standard_key(), Some(StandardKey::W));
+        assert_eq!(WinitKey::Logical(Key::Character(SmolStr::new("ab"))).to_standard_key(), None);
+    }
+
+    #[test]
+    fn mouse_events_fall_back_to_physical_code() {
+        let mut layer = layer();
+        layer.handle_mouse_button(MouseButton::Left, ElementState::Pressed);
+        let event = &layer.poll_events()[0];
+        assert_eq!(event.logical, None);
+        assert_eq!(event.logical_key(), WinitKey::Mouse(MouseButton::Left));
+        assert_eq!(event.device_kind(), StandardDevice::Mouse);
+    }
+
+    #[test]
+    fn key_text_is_committed_on_press_only() {
+        let mut layer = layer();
+        let key = Key::Character(SmolStr::new("h"));
+        layer.handle_key(PhysicalKey::Code(KeyCode::KeyH), &key, ElementState::Pressed, false, Some("h"));
+        layer.handle_key(PhysicalKey::Code(KeyCode::KeyH), &key, ElementState::Released, false, Some("h"));
+        layer.handle_key(PhysicalKey::Code(KeyCode::KeyI), &key, ElementState::Pressed, true, Some("i"));
+        // Los caracteres de control (Enter, Backspace...) no son texto.
+        let enter = Key::Named(NamedKey::Enter);
+        layer.handle_key(PhysicalKey::Code(KeyCode::Enter), &enter, ElementState::Pressed, false, Some("\r"));
+
+        assert_eq!(layer.poll_text(), [TextInputEvent::Commit("hi".into())]);
+        let repeats: Vec<bool> = layer.poll_events().iter().map(|e| e.repeat).collect();
+        assert_eq!(repeats, [false, false, true, false]);
+    }
+
+    #[test]
+    fn ime_preedit_and_commit() {
+        let mut layer = layer();
+        layer.handle_window_event(&WindowEvent::Ime(Ime::Enabled));
+        layer.handle_window_event(&WindowEvent::Ime(Ime::Preedit("ka".into(), Some((2, 2)))));
+        layer.handle_window_event(&WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+        layer.handle_window_event(&WindowEvent::Ime(Ime::Commit("か".into())));
+        layer.handle_window_event(&WindowEvent::Ime(Ime::Disabled));
+
+        assert_eq!(
+            layer.poll_text(),
+            [
+                TextInputEvent::Preedit { text: "ka".into(), cursor: Some(2..2) },
+                TextInputEvent::PreeditCleared,
+                TextInputEvent::Commit("か".into()),
+                TextInputEvent::PreeditCleared,
+            ]
+        );
+        assert!(layer.poll_events().is_empty());
+    }
+
+    #[test]
+    fn line_wheel_deltas_pass_through() {
+        let mut layer = layer();
+        let event = WindowEvent::MouseWheel {
+            device_id: DeviceId::dummy(),
+            delta: MouseScrollDelta::LineDelta(-2.0, 1.0),
+            phase: winit::event::TouchPhase::Moved,
+        };
+        assert!(layer.handle_window_event(&event));
+        assert_eq!(wheel(&mut layer), [(WinitKey::MouseWheel, Some(1.0)), (WinitKey::MouseWheelHorizontal, Some(-2.0))]);
+    }
+
+    #[test]
+    fn pixel_wheel_deltas_are_converted_to_lines() {
+        let mut layer = layer().with_pixels_per_line(20.0);
+        layer.handle_wheel(MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, -60.0)));
+        assert_eq!(wheel(&mut layer), [(WinitKey::MouseWheel, Some(-3.0))]);
+
+        let mut layer = WinitRawLayer::<WinitKey>::new();
+        layer.handle_wheel(MouseScrollDelta::PixelDelta(PhysicalPosition::new(80.0, 0.0)));
+        assert_eq!(wheel(&mut layer), [(WinitKey::MouseWheelHorizontal, Some(80.0 / DEFAULT_PIXELS_PER_LINE))]);
+    }
+
+    #[test]
+    fn invalid_pixels_per_line_keeps_default() {
+        for pixels in [0.0, -10.0, f32::NAN, f32::INFINITY] {
+            let mut layer = layer().with_pixels_per_line(pixels);
+            layer.handle_wheel(MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, DEFAULT_PIXELS_PER_LINE as f64)));
+            assert_eq!(wheel(&mut layer), [(WinitKey::MouseWheel, Some(1.0))]);
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use core::time::Duration;
+
 use crate::layers::{
+    action_events::ActionEvent,
     history::HistoryStateExt,
     mapping_layer::MappingLayerState,
     processing_layer::ProcessingLayerState,
@@ -25,13 +28,15 @@ use crate::layers::{
 /// - Lectura de acciones: presionadas, liberadas y sostenidas.
 /// - Lectura de valores analógicos normalizados (ejes, triggers, intensidades).
 /// - Lectura de la duración temporal de una acción.
+/// - Lectura de la cola ordenada de eventos de acción del frame.
 /// - Acceso **solo lectura** a las capas internas del sistema de entrada:
 ///   - MappingLayer (asociación acción → inputs)
 ///   - ProcessingLayer (transiciones y estados derivados)
 ///   - HistoryLayer (registro temporal de eventos)
 ///
 /// No permite mutación. Toda la modificación del sistema de entrada ocurre en
-/// `RuntimeInputExt`.
+/// `RuntimeInputExt`. La única excepción es el consumo de entradas
+/// buffereadas o dentro de una ventana de gracia (`consume_*`).
 ///
 /// ---------------------------------------------------------------------------
 /// ## Motivación del diseño
@@ -97,6 +102,9 @@ pub trait GameContract {
     /// Devuelve `true` mientras la acción permanezca activa.
     ///
     /// Esta función no distingue entre frames; refleja el estado actual inmediato.
+    ///
+    /// Para acciones en modo `HoldMode::Toggle` refleja el estado conmutado,
+    /// sin que el gameplay tenga que distinguirlo.
     fn action_held(&self, action: &str) -> bool;
 
     /// Retorna un **valor analógico** normalizado asociado a la acción.
@@ -114,6 +122,78 @@ pub trait GameContract {
     /// - medir interacción prolongada
     fn action_duration(&self, action: &str) -> f32;
 
+    // -----------------------------------------------------------------------
+    // Cola de eventos de acción
+    // -----------------------------------------------------------------------
+
+    /// Eventos de acción del frame actual, en orden cronológico.
+    ///
+    /// A diferencia de `action_pressed`, conserva cada transición por
+    /// separado: dos pulsaciones dentro del mismo frame producen dos
+    /// eventos `Performed`, cada uno con su timestamp y dispositivo.
+    fn action_events(&self) -> &[ActionEvent<<Self::MappingLayer as MappingLayerState>::DeviceKind>];
+
+    /// Número de veces que la acción se disparó (`Performed`) en este frame.
+    fn action_press_count(&self, action: &str) -> usize;
+
+    /// Devuelve `true` si el estado actual de la acción proviene de una
+    /// entrada sintética (inyección, macros, bots, tutoriales).
+    ///
+    /// Pensado para diagnósticos y replays; el gameplay no debería
+    /// comportarse distinto según su valor.
+    fn action_is_synthetic(&self, action: &str) -> bool;
+
+    // -----------------------------------------------------------------------
+    // Input buffering
+    // -----------------------------------------------------------------------
+
+    /// Devuelve `true` si existe una pulsación de la acción dentro de su
+    /// ventana de buffer que aún no fue consumida.
+    ///
+    /// No consume la pulsación.
+    fn is_buffered(&self, action: &str) -> bool;
+
+    /// Consume la pulsación buffereada de la acción, si existe.
+    ///
+    /// Retorna `true` una sola vez por pulsación: las llamadas siguientes
+    /// retornan `false` hasta que llegue una nueva pulsación.
+    ///
+    /// Ejemplo: un salto presionado 100 ms antes de aterrizar se ejecuta en
+    /// el primer frame en el que el personaje puede saltar.
+    ///
+    /// Junto con `consume_pressed_within` y `consume_released_within`, es de
+    /// las únicas consultas de este trait con efecto observable; las
+    /// implementaciones registran el consumo mediante mutabilidad interior
+    /// (p. ej. `Cell`), sin alterar el resto del estado del frame.
+    fn consume_buffered(&self, action: &str) -> bool;
+
+    // -----------------------------------------------------------------------
+    // Ventanas de gracia ("coyote time")
+    // -----------------------------------------------------------------------
+
+    /// Devuelve `true` si la acción fue presionada dentro de los últimos `window`.
+    ///
+    /// Se calcula con los timestamps de `HistoryLayer`
+    /// (`current_time` y `last_event_time` de las teclas asignadas a la acción),
+    /// por lo que es independiente del framerate.
+    fn action_pressed_within(&self, action: &str, window: Duration) -> bool;
+
+    /// Devuelve `true` si la acción fue liberada dentro de los últimos `window`.
+    ///
+    /// Ejemplo: "se permite saltar hasta 80 ms después de salir de una
+    /// plataforma" consultando la acción de apoyo del personaje.
+    fn action_released_within(&self, action: &str, window: Duration) -> bool;
+
+    /// Igual que `action_pressed_within`, pero consume la pulsación:
+    /// retorna `true` una sola vez por pulsación.
+    ///
+    /// Comparte el registro de consumo con `consume_buffered`.
+    fn consume_pressed_within(&self, action: &str, window: Duration) -> bool;
+
+    /// Igual que `action_released_within`, pero consume la liberación:
+    /// retorna `true` una sola vez por liberación.
+    fn consume_released_within(&self, action: &str, window: Duration) -> bool;
+
     // -----------------------------------------------------------------------
     // Acceso de solo lectura a las capas internas
     // -----------------------------------------------------------------------
@@ -1,2 +1,3 @@
 pub mod game_contract;
-pub mod runtime_contract;
\ No newline at end of file
+pub mod runtime_contract;
+pub mod subscription_contract;
\ No newline at end of file
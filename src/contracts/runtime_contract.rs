@@ -1,11 +1,31 @@
+use alloc::vec::Vec;
+
 use crate::layers::{
+    action_events::ActionEvent,
+    filters::InputFilterExt,
+    input_macros::MacroEngineExt,
     history::HistoryControlExt,
-    mapping_layer::MappingLayerControl,
+    mapping_layer::{MappingLayerControl, MappingLayerState},
     processing_layer::ProcessingLayerControl,
-    raw_layer::KeyEventExt,
+    raw_layer::{KeyEventExt, RawSignal, TextInputEvent},
+};
+
+use super::{
+    game_contract::GameContract,
+    subscription_contract::{DeviceChange, InputSubscriptionExt},
 };
 
-use super::game_contract::GameContract;
+/// ---------------------------------------------------------------------------
+/// Modo de inyección de una acción sintética.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ActionInjection {
+    /// Pulsa la tecla asignada a la acción; pasa por la capa de mapeo.
+    #[default]
+    ThroughMapping,
+    /// Activa la acción directamente, sin consultar bindings.
+    BypassMapping,
+}
 
 /// ---------------------------------------------------------------------------
 /// **RuntimeInputExt**
@@ -30,6 +50,9 @@ use super::game_contract::GameContract;
 /// runtime.begin_frame();
 ///
 /// // 2) Inyectar eventos crudos provenientes del backend/plataforma
+/// for signal in backend.poll_signals() {
+///     runtime.push_signal(signal);
+/// }
 /// for event in backend.poll_events() {
 ///     runtime.push_raw_event(event);
 /// }
@@ -75,6 +98,26 @@ pub trait RuntimeInputExt {
     /// Implementación concreta de la capa de mapeo (control/mutación).
     type MappingControl: MappingLayerControl;
 
+    /// Filtro entre los eventos crudos y la capa procesada
+    /// (teclas persistentes, lentas, de rebote...).
+    type InputFilter: InputFilterExt<Event = Self::Event>;
+
+    /// Motor de macros y turbo.
+    ///
+    /// Usa el tipo de tecla de los eventos, de modo que el runtime puede
+    /// llamar a `tick` con sus propias capas de mapeo y procesamiento.
+    type MacroEngine: MacroEngineExt<KeyCode = <Self::Event as KeyEventExt>::KeyCode>;
+
+    /// Registro de suscripciones push (acciones, contextos, bindings, dispositivos).
+    ///
+    /// Usa los tipos de contexto y tecla de la capa de mapeo, de modo que el
+    /// runtime puede encolar notificaciones construidas con su propio estado.
+    type Subscriptions: InputSubscriptionExt<
+            Ctx = <Self::MappingControl as MappingLayerControl>::Ctx,
+            KeyCode = <Self::MappingControl as MappingLayerControl>::KeyCode,
+            DeviceKind = <Self::Event as KeyEventExt>::DeviceKind,
+        >;
+
     // -----------------------------------------------------------------------
     // CICLO DE VIDA DEL FRAME
     // -----------------------------------------------------------------------
@@ -92,21 +135,91 @@ pub trait RuntimeInputExt {
     /// Inyecta un evento crudo en el runtime.
     ///
     /// Normalmente:
-    /// - se registra el evento en `HistoryControl`,
+    /// - se pasa por `InputFilter`,
+    /// - se registra el evento resultante en `HistoryControl`,
     /// - se pasa a `ProcessingControl` para actualizar estados inmediatos.
     ///
     /// El orden exacto queda a criterio de la implementación del runtime.
     fn push_raw_event(&mut self, event: Self::Event);
 
+    /// Inyecta una señal de foco o suspensión proveniente de la Raw Layer.
+    ///
+    /// Normalmente se delega en `ProcessingControl::handle_signal`, que libera
+    /// las teclas mantenidas al perder el foco.
+    fn push_signal(&mut self, signal: RawSignal);
+
+    /// Inyecta un evento del canal de texto proveniente de la Raw Layer.
+    ///
+    /// Normalmente se delega en `ProcessingControl::update_text`.
+    fn push_text_event(&mut self, event: TextInputEvent);
+
+    // -----------------------------------------------------------------------
+    // INYECCIÓN SINTÉTICA (bots, tests, tutoriales)
+    // -----------------------------------------------------------------------
+
+    /// Presiona (`pressed = true`) o libera una tecla de forma programática,
+    /// sin backend real.
+    ///
+    /// El evento se procesa en el frame actual con
+    /// `ProcessingControl::inject_synthetic` y queda marcado como
+    /// `InputOrigin::Synthetic`.
+    fn inject_key(&mut self, key: <Self::Event as KeyEventExt>::KeyCode, pressed: bool);
+
+    /// Fija el valor de una entrada analógica de forma programática.
+    ///
+    /// El valor se mantiene hasta que otro evento lo cambie.
+    fn inject_axis(&mut self, key: <Self::Event as KeyEventExt>::KeyCode, value: f32);
+
+    /// Dispara una acción durante `frames` frames.
+    ///
+    /// - `ActionInjection::ThroughMapping`: pulsa la tecla asignada a la
+    ///   acción en el contexto activo, que se resuelve por el camino normal
+    ///   (respeta contextos, toggles, filtros...). Retorna `false` si la
+    ///   acción no tiene tecla.
+    /// - `ActionInjection::BypassMapping`: activa la acción directamente con
+    ///   `MappingControl::inject_action`, aunque no tenga tecla asignada.
+    fn inject_action(&mut self, action: &str, frames: u32, mode: ActionInjection) -> bool;
+
+    /// Notifica la conexión o desconexión de un dispositivo.
+    ///
+    /// La notificación se encola en `Subscriptions` y se despacha en
+    /// `end_frame`.
+    fn push_device_change(&mut self, device: <Self::Event as KeyEventExt>::DeviceKind, change: DeviceChange);
+
     /// Completa el procesamiento del frame de entrada.
     ///
     /// Este método debe:
     /// - finalizar el procesamiento de `ProcessingControl` (transiciones, etc.),
     /// - actualizar el historial en `HistoryControl`,
     /// - invocar a `MappingControl` para resolver acciones,
+    /// - avanzar `MacroEngine` (sus eventos se inyectan en el siguiente frame),
+    /// - despachar las notificaciones pendientes de `Subscriptions`,
     /// - dejar `GameLayer` listo para ser consultado por el motor.
+    ///
+    /// El despacho de notificaciones es el último paso: ningún callback
+    /// se ejecuta a mitad de la resolución.
     fn end_frame(&mut self);
 
+    /// Extrae los eventos de acción pendientes del frame, en orden
+    /// cronológico, dejando la cola vacía.
+    ///
+    /// Los eventos no drenados se descartan en el siguiente `begin_frame`.
+    fn drain_action_events(&mut self) -> Vec<ActionEvent<<<Self::GameLayer as GameContract>::MappingLayer as MappingLayerState>::DeviceKind>>;
+
+    /// Igual que `drain_action_events`, pero añade los eventos al final de
+    /// `out`.
+    ///
+    /// La implementación por defecto delega en `drain_action_events` y
+    /// asigna en cada llamada. Los runtimes deben sobrescribirla (p. ej.
+    /// delegando en `MappingLayerControl::drain_action_events_into`) para
+    /// que reutilizar `out` no asigne.
+    fn drain_action_events_into(
+        &mut self,
+        out: &mut Vec<ActionEvent<<<Self::GameLayer as GameContract>::MappingLayer as MappingLayerState>::DeviceKind>>,
+    ) {
+        out.append(&mut self.drain_action_events());
+    }
+
     // -----------------------------------------------------------------------
     // ACCESO A CAPAS INTERNAS (MUTACIÓN)
     // -----------------------------------------------------------------------
@@ -135,6 +248,28 @@ pub trait RuntimeInputExt {
     /// - cargar perfiles de control, etc.
     fn mapping_mut(&mut self) -> &mut Self::MappingControl;
 
+    /// Acceso mutable al filtro de entrada.
+    ///
+    /// Útil para:
+    /// - aplicar los filtros de accesibilidad del perfil activo,
+    /// - mostrar modificadores enganchados en la UI.
+    fn input_filter_mut(&mut self) -> &mut Self::InputFilter;
+
+    /// Acceso mutable al motor de macros.
+    ///
+    /// Útil para:
+    /// - aplicar la política de macros del modo de juego,
+    /// - iniciar o cancelar macros desde herramientas.
+    fn macro_engine_mut(&mut self) -> &mut Self::MacroEngine;
+
+    /// Acceso mutable al registro de suscripciones.
+    ///
+    /// Útil para:
+    /// - suscribir UI o audio a fases de acción,
+    /// - reaccionar a cambios de contexto o rebinding,
+    /// - detectar conexión de mandos.
+    fn subscriptions_mut(&mut self) -> &mut Self::Subscriptions;
+
     // -----------------------------------------------------------------------
     // CAPA FINAL DE JUEGO (SOLO LECTURA)
     // -----------------------------------------------------------------------
@@ -0,0 +1,173 @@
+use alloc::string::String;
+
+use crate::layers::action_events::{ActionEvent, ActionPhase};
+
+/// ---------------------------------------------------------------------------
+/// Identificador opaco de una suscripción.
+///
+/// Se obtiene al suscribirse y es la única forma de cancelar la suscripción.
+/// Los handles no se reutilizan durante la vida del registro.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionHandle(u64);
+
+impl SubscriptionHandle {
+    /// Crea un handle a partir de su valor interno.
+    ///
+    /// Reservado a las implementaciones de [`InputSubscriptionExt`], que
+    /// deben garantizar que cada valor se entrega una sola vez. El gameplay
+    /// solo debe usar los handles devueltos por `subscribe*`.
+    pub const fn from_raw(id: u64) -> Self {
+        Self(id)
+    }
+
+    /// Valor interno del handle, para las implementaciones del registro.
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+}
+
+/// ---------------------------------------------------------------------------
+/// Cambio de conexión de un dispositivo (hotplug).
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceChange {
+    /// El dispositivo se conectó.
+    Connected,
+    /// El dispositivo se desconectó.
+    Disconnected,
+}
+
+/// ---------------------------------------------------------------------------
+/// Categoría de notificación a la que se puede suscribir.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationKind {
+    /// Fases de acción (`Started`, `Performed`, `Canceled`).
+    Action,
+    /// Cambios del contexto activo (`set_current_context`).
+    ContextSwitched,
+    /// Habilitación o deshabilitación de contextos.
+    ContextToggled,
+    /// Cambios de bindings (`map_action*`, `unmap_action*`, resets, imports).
+    BindingChanged,
+    /// Conexión o desconexión de dispositivos.
+    Device,
+}
+
+/// ---------------------------------------------------------------------------
+/// Notificación entregada a los suscriptores.
+///
+/// - `C`: tipo de contexto (`ContextId`).
+/// - `K`: tipo de tecla (`KeyCodeExt`).
+/// - `D`: tipo de dispositivo (`KeyEventExt::DeviceKind`).
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputNotification<C, K, D> {
+    /// Transición de una acción, idéntica a la de la cola de eventos.
+    Action(ActionEvent<D>),
+    /// El contexto activo cambió de `from` a `to`.
+    ContextSwitched { from: C, to: C },
+    /// Un contexto fue habilitado (`true`) o deshabilitado (`false`).
+    ContextToggled { ctx: C, enabled: bool },
+    /// El binding efectivo de una acción cambió.
+    ///
+    /// `key` es el nuevo binding efectivo (`None` si quedó sin asignar).
+    BindingChanged { ctx: C, action: String, key: Option<K> },
+    /// Un dispositivo se conectó o desconectó.
+    Device { device: D, change: DeviceChange },
+}
+
+impl<C, K, D> InputNotification<C, K, D> {
+    /// Devuelve la categoría de la notificación.
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            InputNotification::Action(_) => NotificationKind::Action,
+            InputNotification::ContextSwitched { .. } => NotificationKind::ContextSwitched,
+            InputNotification::ContextToggled { .. } => NotificationKind::ContextToggled,
+            InputNotification::BindingChanged { .. } => NotificationKind::BindingChanged,
+            InputNotification::Device { .. } => NotificationKind::Device,
+        }
+    }
+}
+
+/// ---------------------------------------------------------------------------
+/// **InputSubscriptionExt**
+///
+/// Registro de suscripciones *push* para subsistemas que prefieren recibir
+/// notificaciones en lugar de consultar `GameContract` cada frame
+/// (UI, audio, tutoriales...).
+///
+/// ---------------------------------------------------------------------------
+/// ## Garantías de despacho
+///
+/// - Las notificaciones se **encolan** durante el frame y se despachan
+///   únicamente dentro de `RuntimeInputExt::end_frame`, después de
+///   `resolve_actions`. Ningún callback se ejecuta a mitad de la resolución.
+/// - El orden es determinista: primero por orden cronológico de la
+///   notificación y, para una misma notificación, por orden de suscripción.
+/// - Suscribirse o cancelar dentro de un callback surte efecto a partir del
+///   siguiente despacho.
+///
+/// ---------------------------------------------------------------------------
+/// ## Ejemplo de uso
+///
+/// ```ignore
+/// let handle = runtime.subscriptions_mut().subscribe_action("jump", Some(ActionPhase::Performed), |n| {
+///     audio.play("jump");
+/// });
+///
+/// // ...
+///
+/// runtime.subscriptions_mut().unsubscribe(handle);
+/// ```
+/// ---------------------------------------------------------------------------
+pub trait InputSubscriptionExt {
+    /// Tipo de contexto de la capa de mapeo.
+    type Ctx;
+
+    /// Tipo de tecla de la capa de mapeo.
+    type KeyCode;
+
+    /// Tipo de dispositivo del backend.
+    type DeviceKind;
+
+    /// Suscribe un callback a una categoría completa de notificaciones.
+    fn subscribe(
+        &mut self,
+        kind: NotificationKind,
+        callback: impl FnMut(&InputNotification<Self::Ctx, Self::KeyCode, Self::DeviceKind>) + 'static,
+    ) -> SubscriptionHandle;
+
+    /// Suscribe un callback a las fases de una acción concreta.
+    ///
+    /// Si `phase` es `None`, recibe todas las fases de la acción.
+    fn subscribe_action(
+        &mut self,
+        action: &str,
+        phase: Option<ActionPhase>,
+        callback: impl FnMut(&InputNotification<Self::Ctx, Self::KeyCode, Self::DeviceKind>) + 'static,
+    ) -> SubscriptionHandle;
+
+    /// Cancela una suscripción.
+    ///
+    /// Retorna `false` si el handle no existe o ya fue cancelado.
+    fn unsubscribe(&mut self, handle: SubscriptionHandle) -> bool;
+
+    /// Indica si un handle sigue activo.
+    fn is_subscribed(&self, handle: SubscriptionHandle) -> bool;
+
+    /// Encola una notificación para el próximo despacho.
+    ///
+    /// Lo usa el runtime al detectar cambios de contexto, bindings o
+    /// dispositivos; el gameplay no debería llamarlo.
+    fn enqueue(&mut self, notification: InputNotification<Self::Ctx, Self::KeyCode, Self::DeviceKind>);
+
+    /// Despacha todas las notificaciones encoladas y vacía la cola.
+    ///
+    /// Debe ser llamado solo desde `RuntimeInputExt::end_frame`.
+    fn dispatch(&mut self);
+
+    /// Elimina todas las suscripciones y notificaciones pendientes.
+    fn clear(&mut self);
+}
@@ -0,0 +1,77 @@
+//! ---------------------------------------------------------------------------
+//! Cola de **eventos de acción** del frame.
+//!
+//! Las consultas booleanas de `GameContract` (`action_pressed`, ...) colapsan
+//! todo lo ocurrido en un frame en un único valor: dos pulsaciones dentro del
+//! mismo frame se leen como una sola.
+//!
+//! La cola de eventos conserva, en orden cronológico y con precisión
+//! sub-frame, cada transición de cada acción:
+//!
+//! - la acción afectada,
+//! - la fase ([`ActionPhase`]),
+//! - el dispositivo de origen y si la entrada fue sintética,
+//! - el timestamp del evento crudo que la originó.
+//!
+//! La cola la llena `MappingLayerControl::resolve_actions`, se expone en
+//! lectura mediante `GameContract::action_events` y se vacía con
+//! `RuntimeInputExt::drain_action_events` o, como muy tarde, en el
+//! siguiente `begin_frame`.
+//!
+//! Útil para juegos de ritmo, juegos de pelea y cualquier lógica que
+//! necesite el orden exacto de las entradas.
+//! ---------------------------------------------------------------------------
+
+use alloc::sync::Arc;
+
+use super::timestamp::Timestamp;
+
+use crate::layers::processing_layer::InputOrigin;
+
+
+/// Fase de una acción dentro de la cola de eventos.
+///
+/// Para una acción digital simple (un botón):
+/// - al presionar se emiten `Started` y `Performed`,
+/// - al soltar se emite `Canceled`.
+///
+/// Para acciones con condición (mantener, doble toque...), `Performed` se
+/// emite solo cuando la condición se cumple, y `Canceled` si la entrada
+/// termina antes o después de cumplirla.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionPhase {
+    /// La entrada asociada a la acción comenzó.
+    Started,
+    /// La acción se disparó.
+    Performed,
+    /// La entrada asociada terminó (liberada, interrumpida o invalidada).
+    Canceled,
+}
+
+
+/// Evento individual de la cola de acciones.
+///
+/// `D` es el tipo de dispositivo del backend (`KeyEventExt::DeviceKind`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionEvent<D> {
+    /// Nombre de la acción afectada.
+    ///
+    /// Es un `Arc<str>` para que emitir un evento no asigne: la capa de
+    /// mapeo guarda una copia por acción y la clona en cada evento.
+    pub action: Arc<str>,
+    /// Fase de la acción.
+    pub phase: ActionPhase,
+    /// Dispositivo que generó el evento crudo original.
+    ///
+    /// `None` para eventos sintéticos.
+    pub device: Option<D>,
+    /// Origen del evento: dispositivo real o inyección sintética.
+    pub origin: InputOrigin,
+    /// Timestamp del evento crudo original (`KeyEventExt::timestamp`) o
+    /// instante programado del evento sintético.
+    pub timestamp: Timestamp,
+    /// Valor analógico de la acción en el momento del evento.
+    ///
+    /// Mismo rango que `GameContract::action_value`.
+    pub value: f32,
+}
@@ -0,0 +1,332 @@
+//! ---------------------------------------------------------------------------
+//! Generación de **hojas de controles** a partir del estado de mapeo.
+//!
+//! Recorre `MappingLayerState` (`contexts`, `actions_in`, `bindings_in`) y
+//! produce una referencia de controles siempre sincronizada con los bindings
+//! reales, en tres formatos:
+//!
+//! - Markdown (wikis, notas de parche),
+//! - HTML (webs, manuales),
+//! - texto plano (consola, QA).
+//!
+//! La hoja agrupa por contexto, muestra el binding principal y los
+//! alternativos con sus nombres legibles ([`KeyDisplay`]) y marca los
+//! contextos deshabilitados.
+//!
+//! ```ignore
+//! let sheet = ControlsSheet::from_mapping(&mapping, &StandardKeyDisplay::new(EnglishKeyNames), DeviceFamily::Keyboard);
+//! std::fs::write("CONTROLS.md", sheet.render(SheetFormat::Markdown, "Controls"))?;
+//! ```
+//! ---------------------------------------------------------------------------
+
+use alloc::{borrow::ToOwned, string::String, string::ToString, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::layers::{
+    key_display::{DeviceFamily, KeyDisplay, KeyLabel},
+    mapping_layer::MappingLayerState,
+};
+
+
+/// Formato de salida de la hoja de controles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SheetFormat {
+    /// Markdown con una tabla por contexto.
+    Markdown,
+    /// Fragmento HTML con una tabla por contexto.
+    Html,
+    /// Texto plano, una línea por acción.
+    PlainText,
+}
+
+/// Fila de la hoja: una acción y todas sus teclas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetRow {
+    /// Nombre de la acción.
+    pub action: String,
+    /// Teclas asignadas; la primera es el binding principal.
+    pub keys: Vec<KeyLabel>,
+}
+
+/// Sección de la hoja: un contexto con sus acciones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetSection {
+    /// Nombre del contexto.
+    pub context: String,
+    /// `false` si el contexto está deshabilitado.
+    pub enabled: bool,
+    /// Acciones del contexto, en el orden de `actions_in`.
+    pub rows: Vec<SheetRow>,
+}
+
+/// Hoja de controles completa, independiente del formato de salida.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ControlsSheet {
+    /// Secciones en el orden de `MappingLayerState::contexts`.
+    pub sections: Vec<SheetSection>,
+}
+
+impl ControlsSheet {
+    /// Construye la hoja recorriendo todos los contextos del estado de mapeo.
+    ///
+    /// Las acciones sin teclas asignadas se incluyen con la lista vacía.
+    pub fn from_mapping<M, D>(mapping: &M, display: &D, family: DeviceFamily) -> Self
+    where
+        M: MappingLayerState,
+        M::Ctx: Display,
+        D: KeyDisplay<M::KeyCode>,
+    {
+        let sections = mapping
+            .contexts()
+            .iter()
+            .map(|ctx| SheetSection {
+                context: ctx.to_string(),
+                enabled: mapping.is_context_enabled(ctx),
+                rows: mapping
+                    .actions_in(ctx)
+                    .iter()
+                    .map(|action| SheetRow {
+                        action: action.clone(),
+                        keys: display.display_binding_in(mapping, ctx, action, family),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self { sections }
+    }
+
+    /// Renderiza la hoja en el formato indicado, con `title` como encabezado.
+    pub fn render(&self, format: SheetFormat, title: &str) -> String {
+        let mut out = String::new();
+        // Escribir en un `String` no puede fallar.
+        let _ = match format {
+            SheetFormat::Markdown => self.write_markdown(&mut out, title),
+            SheetFormat::Html => self.write_html(&mut out, title),
+            SheetFormat::PlainText => self.write_plain_text(&mut out, title),
+        };
+        out
+    }
+
+    fn write_markdown(&self, out: &mut String, title: &str) -> core::fmt::Result {
+        writeln!(out, "# {}", escape_markdown(title))?;
+        for section in &self.sections {
+            writeln!(out)?;
+            write!(out, "## {}", escape_markdown(&section.context))?;
+            if !section.enabled {
+                write!(out, " *(disabled)*")?;
+            }
+            writeln!(out)?;
+            writeln!(out)?;
+            writeln!(out, "| Action | Primary | Alternates |")?;
+            writeln!(out, "|---|---|---|")?;
+            for row in &section.rows {
+                let (primary, alternates) = split_keys(&row.keys);
+                writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    escape_markdown(&row.action),
+                    escape_markdown(&primary),
+                    escape_markdown(&alternates),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_html(&self, out: &mut String, title: &str) -> core::fmt::Result {
+        writeln!(out, "<h1>{}</h1>", escape_html(title))?;
+        for section in &self.sections {
+            if section.enabled {
+                writeln!(out, "<section>")?;
+                writeln!(out, "<h2>{}</h2>", escape_html(&section.context))?;
+            } else {
+                writeln!(out, "<section class=\"disabled\">")?;
+                writeln!(out, "<h2>{} <small>(disabled)</small></h2>", escape_html(&section.context))?;
+            }
+            writeln!(out, "<table>")?;
+            writeln!(out, "<tr><th>Action</th><th>Primary</th><th>Alternates</th></tr>")?;
+            for row in &section.rows {
+                let (primary, alternates) = split_keys(&row.keys);
+                writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&row.action),
+                    escape_html(&primary),
+                    escape_html(&alternates),
+                )?;
+            }
+            writeln!(out, "</table>")?;
+            writeln!(out, "</section>")?;
+        }
+        Ok(())
+    }
+
+    fn write_plain_text(&self, out: &mut String, title: &str) -> core::fmt::Result {
+        writeln!(out, "{title}")?;
+        writeln!(out, "{}", "=".repeat(title.chars().count()))?;
+        for section in &self.sections {
+            writeln!(out)?;
+            write!(out, "[{}]", section.context)?;
+            if !section.enabled {
+                write!(out, " (disabled)")?;
+            }
+            writeln!(out)?;
+            for row in &section.rows {
+                let (primary, alternates) = split_keys(&row.keys);
+                write!(out, "  {}: {}", row.action, primary)?;
+                if !alternates.is_empty() {
+                    write!(out, " (alt: {alternates})")?;
+                }
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Separa el binding principal de los alternativos, ya formateados.
+///
+/// Una acción sin teclas se presenta como `"-"`.
+fn split_keys(keys: &[KeyLabel]) -> (String, String) {
+    match keys.split_first() {
+        Some((primary, rest)) => (
+            primary.name.clone(),
+            rest.iter().map(|k| k.name.as_str()).collect::<Vec<_>>().join(", "),
+        ),
+        None => ("-".to_owned(), String::new()),
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '|' | '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '&') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        layers::{
+            key_display::{EnglishKeyNames, StandardKeyDisplay},
+            standard_keys::StandardKey,
+        },
+        testing::TestMapping,
+    };
+
+    fn sheet() -> ControlsSheet {
+        let mapping = TestMapping::default()
+            .with_context(
+                "Gameplay",
+                &[
+                    ("Jump", &[StandardKey::Space, StandardKey::W]),
+                    ("Walk | Run", &[StandardKey::ShiftLeft]),
+                    ("Zoom <in> & out", &[StandardKey::MouseWheel]),
+                    ("Unbound", &[]),
+                ],
+            )
+            .with_context("Menu <debug>", &[("Back", &[StandardKey::Escape])])
+            .disabled("Menu <debug>");
+        ControlsSheet::from_mapping(&mapping, &StandardKeyDisplay::new(EnglishKeyNames), DeviceFamily::Keyboard)
+    }
+
+    const TITLE: &str = "Controls & <Keys>";
+
+    #[test]
+    fn markdown_golden() {
+        let expected = r"# Controls \& \<Keys\>
+
+## Gameplay
+
+| Action | Primary | Alternates |
+|---|---|---|
+| Jump | Space | W |
+| Walk \| Run | Left Shift |  |
+| Zoom \<in\> \& out | Mouse Wheel |  |
+| Unbound | - |  |
+
+## Menu \<debug\> *(disabled)*
+
+| Action | Primary | Alternates |
+|---|---|---|
+| Back | Escape |  |
+";
+        assert_eq!(sheet().render(SheetFormat::Markdown, TITLE), expected);
+    }
+
+    #[test]
+    fn html_golden() {
+        let expected = "\
+<h1>Controls &amp; &lt;Keys&gt;</h1>
+<section>
+<h2>Gameplay</h2>
+<table>
+<tr><th>Action</th><th>Primary</th><th>Alternates</th></tr>
+<tr><td>Jump</td><td>Space</td><td>W</td></tr>
+<tr><td>Walk | Run</td><td>Left Shift</td><td></td></tr>
+<tr><td>Zoom &lt;in&gt; &amp; out</td><td>Mouse Wheel</td><td></td></tr>
+<tr><td>Unbound</td><td>-</td><td></td></tr>
+</table>
+</section>
+<section class=\"disabled\">
+<h2>Menu &lt;debug&gt; <small>(disabled)</small></h2>
+<table>
+<tr><th>Action</th><th>Primary</th><th>Alternates</th></tr>
+<tr><td>Back</td><td>Escape</td><td></td></tr>
+</table>
+</section>
+";
+        assert_eq!(sheet().render(SheetFormat::Html, TITLE), expected);
+    }
+
+    #[test]
+    fn plain_text_golden() {
+        let expected = "\
+Controls & <Keys>
+=================
+
+[Gameplay]
+  Jump: Space (alt: W)
+  Walk | Run: Left Shift
+  Zoom <in> & out: Mouse Wheel
+  Unbound: -
+
+[Menu <debug>] (disabled)
+  Back: Escape
+";
+        assert_eq!(sheet().render(SheetFormat::PlainText, TITLE), expected);
+    }
+
+    #[test]
+    fn sheet_follows_mapping_order() {
+        let sheet = sheet();
+        let contexts: Vec<_> = sheet.sections.iter().map(|s| (s.context.as_str(), s.enabled)).collect();
+        assert_eq!(contexts, [("Gameplay", true), ("Menu <debug>", false)]);
+        let jump = &sheet.sections[0].rows[0];
+        assert_eq!(jump.keys.iter().map(|k| k.name.as_str()).collect::<Vec<_>>(), ["Space", "W"]);
+        assert!(sheet.sections[0].rows[3].keys.is_empty());
+    }
+}
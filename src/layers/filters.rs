@@ -0,0 +1,557 @@
+//! ---------------------------------------------------------------------------
+//! Contratos de **filtros de entrada** de la etapa de procesamiento.
+//!
+//! Un filtro se sitúa entre la salida de `RawInputLayer::poll_events` y
+//! `ProcessingLayerControl::update`:
+//!
+//! ```text
+//! RawInputLayer  →  InputFilterExt  →  ProcessingLayerControl::update
+//! ```
+//!
+//! Recibe los eventos crudos del frame y devuelve la secuencia de eventos
+//! que la capa de procesamiento debe ver: puede descartar, retener o
+//! reordenar eventos, pero nunca inventa teclas que no existieron.
+//!
+//! Su uso principal es reproducir dentro del motor las funciones de
+//! accesibilidad clásicas de los sistemas operativos
+//! ([`AccessibilityFilters`]), configurables por perfil de usuario.
+//! [`StandardInputFilter`] es la implementación de referencia para eventos
+//! con [`StandardKeyState`].
+//! ---------------------------------------------------------------------------
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use super::timestamp::Timestamp;
+
+use crate::layers::{
+    raw_layer::{KeyEventExt, KeyEventRestampExt},
+    standard_keys::{StandardKey, StandardKeyState, ToStandardKey},
+};
+
+
+/// Configuración de **teclas especiales persistentes** (sticky keys).
+///
+/// Pulsar y soltar un modificador (Shift, Ctrl, Alt, Super) lo deja
+/// "enganchado" hasta la siguiente tecla no modificadora, como si se
+/// hubiese mantenido durante toda su pulsación.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StickyModifiers {
+    /// Si es `true`, pulsar dos veces seguidas un modificador lo bloquea
+    /// hasta una tercera pulsación.
+    pub lock_on_double_press: bool,
+}
+
+/// Conjunto de filtros de accesibilidad.
+///
+/// Cada filtro es opcional; `None` lo desactiva. El valor por defecto
+/// desactiva todos.
+///
+/// Se guarda por perfil de usuario (ver `ProfileManagerControl::set_accessibility_filters`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccessibilityFilters {
+    /// Teclas especiales persistentes.
+    pub sticky_modifiers: Option<StickyModifiers>,
+    /// **Teclas lentas**: tiempo mínimo que una tecla debe mantenerse para
+    /// que su pulsación cuente. Las pulsaciones más cortas se descartan.
+    pub slow_keys: Option<Duration>,
+    /// **Teclas de rebote**: tras soltar una tecla, las nuevas pulsaciones
+    /// de la misma tecla dentro de este intervalo se descartan.
+    pub bounce_keys: Option<Duration>,
+}
+
+impl AccessibilityFilters {
+    /// Indica si algún filtro está activo.
+    pub fn any_enabled(&self) -> bool {
+        self.sticky_modifiers.is_some() || self.slow_keys.is_some() || self.bounce_keys.is_some()
+    }
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Filtro de eventos entre la Raw Layer y la capa de procesamiento.
+///
+/// Los filtros con retención (teclas lentas) necesitan conocer el paso del
+/// tiempo aunque no lleguen eventos nuevos, por eso `filter` recibe el
+/// instante actual del frame.
+///
+/// Garantías:
+/// - El orden relativo de los eventos que se dejan pasar se conserva.
+/// - Un evento retenido se entrega re-estampado con el instante en que se
+///   libera, de modo que la salida sigue en orden cronológico.
+/// - Toda pulsación entregada tiene su liberación correspondiente (no se
+///   generan teclas atascadas).
+/// ---------------------------------------------------------------------------
+pub trait InputFilterExt {
+    /// Tipo de evento crudo filtrado.
+    type Event: KeyEventExt;
+
+    /// Filtra los eventos crudos del frame.
+    ///
+    /// Devuelve, en orden cronológico, los eventos que deben pasarse a
+    /// `ProcessingLayerControl::update`, incluidos los eventos retenidos en
+    /// frames anteriores que ya cumplen su condición en `now`.
+    fn filter(&mut self, events: &[Self::Event], now: Timestamp) -> Vec<Self::Event>;
+
+    /// Igual que `filter`, pero añade los eventos al final de `out`.
+    ///
+    /// La implementación por defecto delega en `filter` y asigna en cada
+    /// llamada. Para reutilizar `out` entre frames sin asignar hay que
+    /// sobrescribirla (como hace [`StandardInputFilter`]).
+    fn filter_into(&mut self, events: &[Self::Event], now: Timestamp, out: &mut Vec<Self::Event>) {
+        out.append(&mut self.filter(events, now));
+    }
+
+    /// Configuración activa.
+    fn config(&self) -> &AccessibilityFilters;
+
+    /// Reemplaza la configuración (p. ej. al cambiar de perfil).
+    ///
+    /// Las pulsaciones retenidas por filtros que se desactivan se descartan
+    /// (junto con su liberación); las liberaciones retenidas se entregan en
+    /// la siguiente llamada a `filter`.
+    fn set_config(&mut self, config: AccessibilityFilters);
+
+    /// Modificadores actualmente enganchados o bloqueados por
+    /// `StickyModifiers`, para mostrarlos en la UI.
+    fn latched_modifiers(&self) -> &[<Self::Event as KeyEventExt>::KeyCode];
+
+    /// Descarta todo evento retenido y los modificadores enganchados.
+    ///
+    /// Debe llamarse al perder el foco (`RawSignal::FocusLost`).
+    fn reset(&mut self);
+}
+
+
+// -----------------------------------------------------------------------------
+// Implementación de referencia
+// -----------------------------------------------------------------------------
+
+/// Indica si la tecla es un modificador para `StickyModifiers`.
+fn is_modifier(key: StandardKey) -> bool {
+    matches!(
+        key,
+        StandardKey::ShiftLeft
+            | StandardKey::ShiftRight
+            | StandardKey::ControlLeft
+            | StandardKey::ControlRight
+            | StandardKey::AltLeft
+            | StandardKey::AltRight
+            | StandardKey::SuperLeft
+            | StandardKey::SuperRight
+    )
+}
+
+/// Modificador enganchado; guarda su liberación original, que se
+/// re-estampa al entregarse.
+#[derive(Debug, Clone)]
+struct Latch<E: KeyEventExt> {
+    key: E::KeyCode,
+    release: E,
+    locked: bool,
+}
+
+/// Implementación de referencia de [`InputFilterExt`].
+///
+/// Aplica los filtros en orden: teclas de rebote, teclas lentas y teclas
+/// persistentes. Los eventos `Changed` (ejes) pasan sin filtrar.
+///
+/// - **Rebote**: una pulsación de la misma tecla dentro del intervalo desde
+///   su última liberación se descarta junto con sus repeticiones y su
+///   liberación.
+/// - **Lentas**: la pulsación se retiene hasta que se cumple el tiempo
+///   mínimo (según el timestamp del siguiente evento o `now`) y se entrega
+///   con ese instante; si la tecla se suelta antes, se descartan pulsación y
+///   liberación.
+/// - **Persistentes**: la liberación de un modificador pulsado sin otra
+///   tecla se retiene hasta que se suelta la siguiente tecla no
+///   modificadora, y se entrega con el timestamp de esa liberación. Volver a pulsar un modificador enganchado lo desengancha,
+///   o lo bloquea con `lock_on_double_press` (y una tercera pulsación lo
+///   libera).
+#[derive(Debug, Clone)]
+pub struct StandardInputFilter<E: KeyEventExt> {
+    config: AccessibilityFilters,
+    /// Última liberación entregada de cada tecla (rebote).
+    last_release: Vec<(E::KeyCode, Timestamp)>,
+    /// Pulsaciones retenidas y el instante en que se entregan (lentas).
+    pending: Vec<(E, Timestamp)>,
+    /// Teclas cuya pulsación se descartó: sus repeticiones y su liberación
+    /// también se descartan.
+    suppressed: Vec<E::KeyCode>,
+    /// Modificadores mantenidos y si se pulsó otra tecla durante su pulsación.
+    held_modifiers: Vec<(E::KeyCode, bool)>,
+    latches: Vec<Latch<E>>,
+    latched: Vec<E::KeyCode>,
+    /// Teclas pulsadas con modificadores enganchados; al soltarse la última,
+    /// los modificadores se liberan.
+    chord_keys: Vec<E::KeyCode>,
+    /// Liberaciones pendientes de entregar tras desactivar un filtro.
+    flush: Vec<E>,
+}
+
+impl<E: KeyEventExt> Default for StandardInputFilter<E> {
+    fn default() -> Self {
+        Self::new(AccessibilityFilters::default())
+    }
+}
+
+impl<E: KeyEventExt> StandardInputFilter<E> {
+    /// Crea el filtro con la configuración indicada.
+    pub fn new(config: AccessibilityFilters) -> Self {
+        Self {
+            config,
+            last_release: Vec::new(),
+            pending: Vec::new(),
+            suppressed: Vec::new(),
+            held_modifiers: Vec::new(),
+            latches: Vec::new(),
+            latched: Vec::new(),
+            chord_keys: Vec::new(),
+            flush: Vec::new(),
+        }
+    }
+}
+
+impl<E> StandardInputFilter<E>
+where
+    E: KeyEventRestampExt<KeyState = StandardKeyState, KeyCode: ToStandardKey + PartialEq + Clone>,
+{
+    fn handle(&mut self, event: &E, out: &mut Vec<E>) {
+        let key = event.keycode();
+        match event.state() {
+            StandardKeyState::Changed => out.push(event.clone()),
+            StandardKeyState::Pressed => {
+                if self.suppressed.contains(&key) || self.pending.iter().any(|(e, _)| e.keycode() == key) {
+                    return;
+                }
+                if let Some(interval) = self.config.bounce_keys
+                    && !event.is_repeat()
+                    && self.last_release.iter().any(|(k, at)| *k == key && event.timestamp() - *at < interval)
+                {
+                    self.suppressed.push(key);
+                    return;
+                }
+                if let Some(hold) = self.config.slow_keys
+                    && !event.is_repeat()
+                {
+                    self.pending.push((event.clone(), event.timestamp() + hold));
+                    return;
+                }
+                self.deliver_press(event, out);
+            }
+            StandardKeyState::Released => {
+                if let Some(index) = self.suppressed.iter().position(|k| *k == key) {
+                    self.suppressed.swap_remove(index);
+                    return;
+                }
+                if let Some(index) = self.pending.iter().position(|(e, _)| e.keycode() == key) {
+                    self.pending.remove(index);
+                    return;
+                }
+                if self.config.bounce_keys.is_some() {
+                    self.last_release.retain(|(k, _)| *k != key);
+                    self.last_release.push((key, event.timestamp()));
+                }
+                self.deliver_release(event, out);
+            }
+        }
+    }
+
+    /// Entrega las pulsaciones retenidas que cumplen su tiempo en `now`.
+    fn release_pending(&mut self, now: Timestamp, out: &mut Vec<E>) {
+        while let Some(index) = self.pending.iter().position(|(_, due)| *due <= now) {
+            let (mut event, due) = self.pending.remove(index);
+            event.restamp(due);
+            self.deliver_press(&event, out);
+        }
+    }
+
+    fn deliver_press(&mut self, event: &E, out: &mut Vec<E>) {
+        let Some(sticky) = self.config.sticky_modifiers else {
+            out.push(event.clone());
+            return;
+        };
+        let key = event.keycode();
+        if !key.to_standard_key().is_some_and(is_modifier) {
+            for (_, used) in &mut self.held_modifiers {
+                *used = true;
+            }
+            if !event.is_repeat() && self.latches.iter().any(|latch| !latch.locked) && !self.chord_keys.contains(&key) {
+                self.chord_keys.push(key);
+            }
+            out.push(event.clone());
+            return;
+        }
+
+        if let Some(index) = self.latches.iter().position(|latch| latch.key == key) {
+            // El modificador ya está pulsado para la capa de procesamiento.
+            if event.is_repeat() {
+                return;
+            }
+            if sticky.lock_on_double_press && !self.latches[index].locked {
+                self.latches[index].locked = true;
+                self.suppressed.push(key);
+            } else {
+                // Su liberación, que llegará después, lo desengancha.
+                self.latches.remove(index);
+                self.latched.retain(|k| *k != key);
+            }
+            return;
+        }
+        if !self.held_modifiers.iter().any(|(k, _)| *k == key) {
+            self.held_modifiers.push((key, false));
+        }
+        out.push(event.clone());
+    }
+
+    fn deliver_release(&mut self, event: &E, out: &mut Vec<E>) {
+        if self.config.sticky_modifiers.is_none() {
+            out.push(event.clone());
+            return;
+        }
+        let key = event.keycode();
+        if let Some(index) = self.held_modifiers.iter().position(|(k, _)| *k == key) {
+            let (key, used) = self.held_modifiers.remove(index);
+            if used {
+                out.push(event.clone());
+            } else {
+                self.latched.push(key.clone());
+                self.latches.push(Latch { key, release: event.clone(), locked: false });
+            }
+            return;
+        }
+
+        out.push(event.clone());
+        if let Some(index) = self.chord_keys.iter().position(|k| *k == key) {
+            self.chord_keys.remove(index);
+            if self.chord_keys.is_empty() {
+                for mut latch in self.latches.extract_if(.., |latch| !latch.locked) {
+                    self.latched.retain(|k| *k != latch.key);
+                    latch.release.restamp(event.timestamp());
+                    out.push(latch.release);
+                }
+            }
+        }
+    }
+
+    /// Suelta los modificadores enganchados, entregando su liberación en el
+    /// siguiente `filter` (re-estampada con el primer evento de esa llamada,
+    /// o con `now` si no hay eventos).
+    fn unlatch_all(&mut self) {
+        self.flush.extend(self.latches.drain(..).map(|latch| latch.release));
+        self.latched.clear();
+        self.held_modifiers.clear();
+        self.chord_keys.clear();
+    }
+}
+
+impl<E> InputFilterExt for StandardInputFilter<E>
+where
+    E: KeyEventRestampExt<KeyState = StandardKeyState, KeyCode: ToStandardKey + PartialEq + Clone>,
+{
+    type Event = E;
+
+    fn filter(&mut self, events: &[E], now: Timestamp) -> Vec<E> {
+        let mut out = Vec::new();
+        self.filter_into(events, now, &mut out);
+        out
+    }
+
+    fn filter_into(&mut self, events: &[E], now: Timestamp, out: &mut Vec<E>) {
+        let at = events.first().map_or(now, |event| event.timestamp());
+        for mut release in self.flush.drain(..) {
+            release.restamp(at);
+            out.push(release);
+        }
+        for event in events {
+            self.release_pending(event.timestamp(), out);
+            self.handle(event, out);
+        }
+        self.release_pending(now, out);
+    }
+
+    fn config(&self) -> &AccessibilityFilters {
+        &self.config
+    }
+
+    fn set_config(&mut self, config: AccessibilityFilters) {
+        if config.slow_keys.is_none() {
+            let pending = self.pending.drain(..).map(|(event, _)| event.keycode());
+            self.suppressed.extend(pending);
+        }
+        if config.bounce_keys.is_none() {
+            self.last_release.clear();
+        }
+        if config.sticky_modifiers.is_none() {
+            self.unlatch_all();
+        }
+        self.config = config;
+    }
+
+    fn latched_modifiers(&self) -> &[E::KeyCode] {
+        &self.latched
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.config);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::layers::standard_keys::{StandardDevice, StandardKeyEvent};
+
+    use StandardKey::*;
+    use StandardKeyState::{Changed, Pressed, Released};
+
+    fn ev(key: StandardKey, state: StandardKeyState, millis: u64) -> StandardKeyEvent {
+        StandardKeyEvent {
+            key,
+            state,
+            timestamp: Timestamp::from_millis(millis),
+            device: StandardDevice::Keyboard,
+            repeat: false,
+            value: None,
+        }
+    }
+
+    fn run(
+        filter: &mut StandardInputFilter<StandardKeyEvent>,
+        events: &[StandardKeyEvent],
+        now: u64,
+    ) -> Vec<(StandardKey, StandardKeyState, u64)> {
+        filter
+            .filter(events, Timestamp::from_millis(now))
+            .iter()
+            .map(|e| (e.key, e.state, e.timestamp.as_duration().as_millis() as u64))
+            .collect()
+    }
+
+    fn with(config: AccessibilityFilters) -> StandardInputFilter<StandardKeyEvent> {
+        StandardInputFilter::new(config)
+    }
+
+    #[test]
+    fn disabled_filter_passes_everything() {
+        let mut filter = with(AccessibilityFilters::default());
+        let events = [ev(A, Pressed, 0), ev(ShiftLeft, Pressed, 1), ev(A, Released, 2), ev(ShiftLeft, Released, 3)];
+        assert_eq!(run(&mut filter, &events, 3).len(), 4);
+        assert!(filter.latched_modifiers().is_empty());
+    }
+
+    #[test]
+    fn bounce_keys_drop_fast_repress_and_its_release() {
+        let mut filter = with(AccessibilityFilters { bounce_keys: Some(Duration::from_millis(100)), ..Default::default() });
+        let events = [
+            ev(A, Pressed, 0),
+            ev(A, Released, 10),
+            ev(A, Pressed, 50),
+            ev(B, Pressed, 60),
+            ev(A, Released, 70),
+            ev(A, Pressed, 200),
+        ];
+        assert_eq!(
+            run(&mut filter, &events, 200),
+            [(A, Pressed, 0), (A, Released, 10), (B, Pressed, 60), (A, Pressed, 200)]
+        );
+    }
+
+    #[test]
+    fn slow_keys_drop_short_presses() {
+        let mut filter = with(AccessibilityFilters { slow_keys: Some(Duration::from_millis(100)), ..Default::default() });
+        assert!(run(&mut filter, &[ev(A, Pressed, 0), ev(A, Released, 50)], 50).is_empty());
+    }
+
+    #[test]
+    fn slow_keys_deliver_held_press_when_due() {
+        let mut filter = with(AccessibilityFilters { slow_keys: Some(Duration::from_millis(100)), ..Default::default() });
+        assert!(run(&mut filter, &[ev(A, Pressed, 0)], 50).is_empty());
+        // Sin eventos nuevos, el paso del tiempo basta.
+        assert_eq!(run(&mut filter, &[], 100), [(A, Pressed, 100)]);
+        assert_eq!(run(&mut filter, &[ev(A, Released, 300)], 300), [(A, Released, 300)]);
+
+        // Un evento posterior al plazo entrega antes la pulsación retenida,
+        // estampada con el instante en que se cumplió.
+        let events = [ev(B, Pressed, 400), ev(C, Pressed, 520)];
+        assert_eq!(run(&mut filter, &events, 520), [(B, Pressed, 500)]);
+        assert_eq!(run(&mut filter, &[], 650), [(C, Pressed, 620)]);
+    }
+
+    #[test]
+    fn axes_are_not_filtered() {
+        let mut filter = with(AccessibilityFilters {
+            slow_keys: Some(Duration::from_millis(100)),
+            bounce_keys: Some(Duration::from_millis(100)),
+            sticky_modifiers: Some(StickyModifiers::default()),
+        });
+        assert_eq!(run(&mut filter, &[ev(MouseWheel, Changed, 0)], 0), [(MouseWheel, Changed, 0)]);
+    }
+
+    #[test]
+    fn sticky_modifier_applies_to_next_key() {
+        let mut filter = with(AccessibilityFilters { sticky_modifiers: Some(StickyModifiers::default()), ..Default::default() });
+        assert_eq!(run(&mut filter, &[ev(ShiftLeft, Pressed, 0), ev(ShiftLeft, Released, 10)], 10), [(ShiftLeft, Pressed, 0)]);
+        assert_eq!(filter.latched_modifiers(), [ShiftLeft]);
+
+        assert_eq!(run(&mut filter, &[ev(A, Pressed, 100)], 100), [(A, Pressed, 100)]);
+        assert_eq!(filter.latched_modifiers(), [ShiftLeft]);
+
+        // Al soltar la tecla se entrega la liberación original del modificador.
+        assert_eq!(run(&mut filter, &[ev(A, Released, 150)], 150), [(A, Released, 150), (ShiftLeft, Released, 150)]);
+        assert!(filter.latched_modifiers().is_empty());
+    }
+
+    #[test]
+    fn modifier_held_through_chord_is_not_latched() {
+        let mut filter = with(AccessibilityFilters { sticky_modifiers: Some(StickyModifiers::default()), ..Default::default() });
+        let events = [ev(ControlLeft, Pressed, 0), ev(C, Pressed, 10), ev(C, Released, 20), ev(ControlLeft, Released, 30)];
+        assert_eq!(run(&mut filter, &events, 30).len(), 4);
+        assert!(filter.latched_modifiers().is_empty());
+    }
+
+    #[test]
+    fn pressing_latched_modifier_again_unlatches_it() {
+        let mut filter = with(AccessibilityFilters { sticky_modifiers: Some(StickyModifiers::default()), ..Default::default() });
+        run(&mut filter, &[ev(AltLeft, Pressed, 0), ev(AltLeft, Released, 10)], 10);
+        let events = [ev(AltLeft, Pressed, 20), ev(AltLeft, Released, 30)];
+        assert_eq!(run(&mut filter, &events, 30), [(AltLeft, Released, 30)]);
+        assert!(filter.latched_modifiers().is_empty());
+    }
+
+    #[test]
+    fn double_press_locks_until_third_press() {
+        let config = StickyModifiers { lock_on_double_press: true };
+        let mut filter = with(AccessibilityFilters { sticky_modifiers: Some(config), ..Default::default() });
+        let lock = [ev(ShiftLeft, Pressed, 0), ev(ShiftLeft, Released, 10), ev(ShiftLeft, Pressed, 20), ev(ShiftLeft, Released, 30)];
+        assert_eq!(run(&mut filter, &lock, 30), [(ShiftLeft, Pressed, 0)]);
+
+        // Bloqueado: sobrevive a varias teclas.
+        let keys = [ev(A, Pressed, 40), ev(A, Released, 50), ev(B, Pressed, 60), ev(B, Released, 70)];
+        assert_eq!(run(&mut filter, &keys, 70).len(), 4);
+        assert_eq!(filter.latched_modifiers(), [ShiftLeft]);
+
+        let unlock = [ev(ShiftLeft, Pressed, 80), ev(ShiftLeft, Released, 90)];
+        assert_eq!(run(&mut filter, &unlock, 90), [(ShiftLeft, Released, 90)]);
+        assert!(filter.latched_modifiers().is_empty());
+    }
+
+    #[test]
+    fn disabling_filters_never_leaves_stuck_keys() {
+        let mut filter = with(AccessibilityFilters {
+            slow_keys: Some(Duration::from_millis(100)),
+            sticky_modifiers: Some(StickyModifiers::default()),
+            ..Default::default()
+        });
+        run(&mut filter, &[ev(ShiftLeft, Pressed, 0)], 100);
+        run(&mut filter, &[ev(ShiftLeft, Released, 110), ev(A, Pressed, 120)], 130);
+
+        filter.set_config(AccessibilityFilters::default());
+        // La pulsación retenida de `A` se descarta con su liberación; la del
+        // modificador enganchado se entrega antes que los eventos nuevos.
+        assert_eq!(run(&mut filter, &[ev(A, Released, 200)], 210), [(ShiftLeft, Released, 200)]);
+        assert!(filter.latched_modifiers().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
-use std::time::Duration;
+use alloc::vec::Vec;
+use core::time::Duration;
 
 use crate::layers::raw_layer::{KeyCodeExt, KeyStateExt};
 
@@ -123,6 +124,20 @@ pub trait HistoryStateExt {
     /// del final del historial.
     fn match_recent_ordered_sequence(&self,sequence: &[Self::KeyCode],max_interval: Duration) -> bool;
 
+    /// Marca de tiempo de referencia del frame actual.
+    ///
+    /// Usa la misma base temporal que los timestamps registrados con
+    /// `add_event`, de modo que `current_time() - t` es la antigüedad
+    /// de un evento registrado en `t`.
+    fn current_time(&self) -> Duration;
+
+    /// Marca de tiempo del evento más reciente de `key` con el estado `state`.
+    ///
+    /// Retorna `None` si no existe ningún evento así en el historial.
+    ///
+    /// Base de las ventanas temporales de la capa de mapeo (input buffering).
+    fn last_event_time(&self,key: &Self::KeyCode,state: &Self::KeyState) -> Option<Duration>;
+
 
 
     /// Proporciona acceso directo al historial completo de eventos.
@@ -0,0 +1,145 @@
+//! ---------------------------------------------------------------------------
+//! Contratos de **macros de entrada** y **turbo** (auto-disparo).
+//!
+//! - Una **macro** es una secuencia temporizada de pasos (teclas, acciones,
+//!   esperas) que se dispara con una acción.
+//! - El **turbo** repite una tecla a una tasa fija mientras se mantiene.
+//!
+//! Las definiciones viven en la capa `User` del mapeo, por lo que se
+//! guardan por perfil y esquema con `export_key_mappings`. El motor de
+//! macros ([`MacroEngineExt`]) las ejecuta inyectando eventos sintéticos en
+//! la capa de procesamiento (`ProcessingLayerControl::inject_synthetic`)
+//! según un calendario determinista.
+//!
+//! Los modos competitivos pueden prohibirlas con [`MacroPolicy::Disallowed`].
+//! ---------------------------------------------------------------------------
+
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use super::timestamp::Timestamp;
+
+use crate::layers::{
+    mapping_layer::MappingLayerState,
+    processing_layer::{ProcessingLayerState, SyntheticKeyEvent},
+};
+
+
+/// Paso individual de una macro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroStep<K> {
+    /// Presiona una tecla (queda mantenida hasta un `Release`).
+    Press(K),
+    /// Libera una tecla.
+    Release(K),
+    /// Presiona y libera una tecla en el mismo instante del calendario.
+    ///
+    /// La liberación se entrega en el frame siguiente a la pulsación, para
+    /// que `just_pressed` sea observable.
+    Tap(K),
+    /// Pulsa la tecla asignada actualmente a la acción en el contexto activo
+    /// (igual que `Tap`). Si la acción no tiene tecla, el paso se omite.
+    Action(String),
+    /// Espera antes del siguiente paso.
+    Wait(Duration),
+}
+
+/// Definición de una macro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMacro<K> {
+    /// Nombre único de la macro dentro del contexto.
+    pub name: String,
+    /// Acción que dispara la macro al presionarse.
+    pub trigger: String,
+    /// Pasos, ejecutados en orden.
+    pub steps: Vec<MacroStep<K>>,
+}
+
+/// Política de uso de macros y turbo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MacroPolicy {
+    /// Macros y turbo permitidos.
+    #[default]
+    Allowed,
+    /// Macros y turbo desactivados (p. ej. partidas competitivas).
+    ///
+    /// Las definiciones se conservan, pero no se ejecutan.
+    Disallowed,
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Motor de ejecución de macros y turbo.
+///
+/// Se ejecuta una vez por frame, después de resolver las acciones
+/// (para detectar disparadores) y antes del siguiente `update` de la capa de
+/// procesamiento (para inyectar sus eventos):
+///
+/// ```ignore
+/// let synthetic = engine.tick(&mapping, &processing, now);
+/// processing.inject_synthetic(&synthetic);
+/// ```
+///
+/// El calendario es determinista: con las mismas entradas y los mismos
+/// instantes `now`, produce exactamente los mismos eventos.
+/// ---------------------------------------------------------------------------
+pub trait MacroEngineExt {
+    type KeyCode;
+
+    /// Avanza el calendario hasta `now` y devuelve, en orden, los eventos
+    /// sintéticos que deben inyectarse en la capa de procesamiento.
+    ///
+    /// - Inicia las macros cuya acción disparadora fue presionada
+    ///   (`MappingLayerState::macros`).
+    /// - Emite las pulsaciones y liberaciones de turbo para las teclas
+    ///   mantenidas con `MappingLayerState::turbo_interval`.
+    ///
+    /// Con `MacroPolicy::Disallowed` no inicia macros ni emite pulsaciones
+    /// de turbo, pero sí entrega las liberaciones pendientes de macros
+    /// canceladas y del turbo detenido, para que ninguna tecla sintética
+    /// quede mantenida.
+    fn tick<M, P>(&mut self, mapping: &M, processing: &P, now: Timestamp) -> Vec<SyntheticKeyEvent<Self::KeyCode>>
+    where
+        M: MappingLayerState<KeyCode = Self::KeyCode>,
+        P: ProcessingLayerState<KeyCode = Self::KeyCode>;
+
+    /// Igual que `tick`, pero añade los eventos al final de `out`.
+    ///
+    /// La implementación por defecto delega en `tick`, por lo que asigna
+    /// un vector en cada frame; un motor que quiera reutilizar `out` sin
+    /// asignar debe sobrescribirla.
+    fn tick_into<M, P>(&mut self, mapping: &M, processing: &P, now: Timestamp, out: &mut Vec<SyntheticKeyEvent<Self::KeyCode>>)
+    where
+        M: MappingLayerState<KeyCode = Self::KeyCode>,
+        P: ProcessingLayerState<KeyCode = Self::KeyCode>,
+    {
+        out.append(&mut self.tick(mapping, processing, now));
+    }
+
+    /// Inicia una macro manualmente (tests, herramientas).
+    ///
+    /// Retorna `false` si la macro no existe o la política lo impide.
+    fn start<M>(&mut self, mapping: &M, name: &str, now: Timestamp) -> bool
+    where
+        M: MappingLayerState<KeyCode = Self::KeyCode>;
+
+    /// Cancela una macro en curso, liberando las teclas que mantenga.
+    ///
+    /// Las liberaciones se entregan en el siguiente `tick`.
+    fn cancel(&mut self, name: &str) -> bool;
+
+    /// Indica si una macro está en curso.
+    fn is_running(&self, name: &str) -> bool;
+
+    /// Política activa.
+    fn policy(&self) -> MacroPolicy;
+
+    /// Cambia la política.
+    ///
+    /// Pasar a `Disallowed` cancela las macros en curso y detiene el turbo;
+    /// sus liberaciones se entregan en el siguiente `tick`, como con `cancel`.
+    fn set_policy(&mut self, policy: MacroPolicy);
+
+    /// Cancela todas las macros y detiene el turbo.
+    fn reset(&mut self);
+}
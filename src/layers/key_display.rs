@@ -0,0 +1,326 @@
+//! ---------------------------------------------------------------------------
+//! Contratos de **presentación de teclas** para menús, tutoriales y
+//! pantallas de configuración.
+//!
+//! Convierte códigos de tecla en:
+//!
+//! - nombres legibles y localizables (`"Left Shift"`, `"Maj gauche"`),
+//! - identificadores de glifo por familia de dispositivo
+//!   (`"ps_cross"`, `"xbox_a"`), que la UI traduce a iconos.
+//!
+//! Las tablas de nombres son intercambiables por idioma ([`KeyNameTable`]);
+//! el crate incluye la tabla inglesa del conjunto estándar
+//! ([`EnglishKeyNames`]).
+//!
+//! Este módulo no dibuja nada ni carga recursos: solo resuelve textos e
+//! identificadores.
+//! ---------------------------------------------------------------------------
+
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use crate::layers::{
+    mapping_layer::MappingLayerState,
+    standard_keys::{StandardKey, ToStandardKey},
+};
+
+
+/// Familia de dispositivo para la que se resuelven los glifos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceFamily {
+    /// Teclado.
+    Keyboard,
+    /// Mouse.
+    Mouse,
+    /// Mandos Xbox y compatibles XInput.
+    Xbox,
+    /// Mandos PlayStation.
+    PlayStation,
+    /// Mandos Nintendo.
+    Nintendo,
+    /// Mando genérico sin iconografía de marca.
+    GenericGamepad,
+}
+
+
+/// Resultado de presentar una tecla: nombre localizado y glifo opcional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyLabel {
+    /// Nombre legible en el idioma de la tabla activa.
+    pub name: String,
+    /// Identificador de glifo para la familia pedida, si existe.
+    pub glyph: Option<String>,
+}
+
+
+/// Tabla de nombres de teclas para un idioma.
+///
+/// Las implementaciones pueden cubrir solo parte del conjunto estándar;
+/// las teclas sin entrada se resuelven con la tabla inglesa.
+pub trait KeyNameTable {
+    /// Etiqueta de idioma (BCP 47), p. ej. `"en"`, `"fr-FR"`.
+    fn locale(&self) -> &str;
+
+    /// Nombre localizado de una tecla estándar.
+    fn key_name(&self, key: StandardKey) -> Option<&str>;
+}
+
+
+/// Tabla de nombres en inglés del conjunto estándar.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnglishKeyNames;
+
+impl KeyNameTable for EnglishKeyNames {
+    fn locale(&self) -> &str {
+        "en"
+    }
+
+    fn key_name(&self, key: StandardKey) -> Option<&str> {
+        Some(key.english_name())
+    }
+}
+
+
+/// Identificador de glifo de una tecla estándar para una familia de dispositivo.
+///
+/// - Gamepads: el icono de marca del botón en esa posición
+///   (`GamepadSouth` → `"xbox_a"`, `"ps_cross"`, `"nintendo_b"`, `"pad_south"`).
+///   Los ejes de los sticks tienen glifo propio por dirección
+///   (`GamepadLeftStickX` → `"xbox_ls_x"`); los ejes de gatillo comparten
+///   el glifo del gatillo (`GamepadLeftTriggerAxis` → `"xbox_lt"`).
+/// - Mouse: el identificador de la tecla tal cual, que ya empieza por
+///   `"mouse_"` (`MouseLeft` → `"mouse_left"`, `MouseWheel` →
+///   `"mouse_wheel"`); incluye botones, movimiento y ruedas.
+/// - Teclado: `"key_<id>"` para las teclas de teclado.
+///
+/// Retorna `None` si la tecla no pertenece a la familia pedida.
+pub fn glyph_id(key: StandardKey, family: DeviceFamily) -> Option<String> {
+    use crate::layers::standard_keys::KeyGroup;
+
+    match (key.group(), family) {
+        (KeyGroup::Keyboard, DeviceFamily::Keyboard) => Some(format!("key_{}", key.id())),
+        (KeyGroup::Mouse, DeviceFamily::Mouse) => Some(key.id().to_owned()),
+        (KeyGroup::Gamepad, DeviceFamily::Xbox) => gamepad_glyph(key, "xbox", XBOX_NAMES),
+        (KeyGroup::Gamepad, DeviceFamily::PlayStation) => gamepad_glyph(key, "ps", PLAYSTATION_NAMES),
+        (KeyGroup::Gamepad, DeviceFamily::Nintendo) => gamepad_glyph(key, "nintendo", NINTENDO_NAMES),
+        (KeyGroup::Gamepad, DeviceFamily::GenericGamepad) => gamepad_glyph(key, "pad", GENERIC_NAMES),
+        _ => None,
+    }
+}
+
+/// Nombres de glifo por posición, en el orden de [`GAMEPAD_ORDER`].
+type GlyphNames = [&'static str; 23];
+
+const GAMEPAD_ORDER: [StandardKey; 23] = [
+    StandardKey::GamepadSouth,
+    StandardKey::GamepadEast,
+    StandardKey::GamepadWest,
+    StandardKey::GamepadNorth,
+    StandardKey::GamepadLeftBumper,
+    StandardKey::GamepadRightBumper,
+    StandardKey::GamepadLeftTrigger,
+    StandardKey::GamepadRightTrigger,
+    StandardKey::GamepadSelect,
+    StandardKey::GamepadStart,
+    StandardKey::GamepadMode,
+    StandardKey::GamepadLeftStick,
+    StandardKey::GamepadRightStick,
+    StandardKey::GamepadDPadUp,
+    StandardKey::GamepadDPadDown,
+    StandardKey::GamepadDPadLeft,
+    StandardKey::GamepadDPadRight,
+    StandardKey::GamepadLeftStickX,
+    StandardKey::GamepadLeftStickY,
+    StandardKey::GamepadRightStickX,
+    StandardKey::GamepadRightStickY,
+    StandardKey::GamepadLeftTriggerAxis,
+    StandardKey::GamepadRightTriggerAxis,
+];
+
+const XBOX_NAMES: GlyphNames = [
+    "a", "b", "x", "y", "lb", "rb", "lt", "rt", "view", "menu", "guide", "ls", "rs",
+    "dpad_up", "dpad_down", "dpad_left", "dpad_right",
+    "ls_x", "ls_y", "rs_x", "rs_y", "lt", "rt",
+];
+
+const PLAYSTATION_NAMES: GlyphNames = [
+    "cross", "circle", "square", "triangle", "l1", "r1", "l2", "r2", "create", "options", "ps", "l3", "r3",
+    "dpad_up", "dpad_down", "dpad_left", "dpad_right",
+    "ls_x", "ls_y", "rs_x", "rs_y", "l2", "r2",
+];
+
+const NINTENDO_NAMES: GlyphNames = [
+    "b", "a", "y", "x", "l", "r", "zl", "zr", "minus", "plus", "home", "ls", "rs",
+    "dpad_up", "dpad_down", "dpad_left", "dpad_right",
+    "ls_x", "ls_y", "rs_x", "rs_y", "zl", "zr",
+];
+
+const GENERIC_NAMES: GlyphNames = [
+    "south", "east", "west", "north", "left_bumper", "right_bumper", "left_trigger", "right_trigger",
+    "select", "start", "mode", "left_stick", "right_stick",
+    "dpad_up", "dpad_down", "dpad_left", "dpad_right",
+    "left_stick_x", "left_stick_y", "right_stick_x", "right_stick_y", "left_trigger", "right_trigger",
+];
+
+fn gamepad_glyph(key: StandardKey, prefix: &str, names: GlyphNames) -> Option<String> {
+    let index = GAMEPAD_ORDER.iter().position(|k| *k == key)?;
+    Some(format!("{prefix}_{}", names[index]))
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Contrato de **presentación de teclas**.
+///
+/// `K` es el tipo de código de tecla de la capa de mapeo.
+///
+/// Las implementaciones resuelven el nombre y el glifo de una tecla; los
+/// métodos provistos (`key_label`, `display_binding`, ...) recorren los
+/// bindings de `MappingLayerState` para presentar acciones completas.
+/// ---------------------------------------------------------------------------
+pub trait KeyDisplay<K> {
+    /// Nombre legible de la tecla en el idioma activo.
+    ///
+    /// Retorna `None` si la tecla no tiene nombre conocido.
+    fn key_name(&self, key: &K) -> Option<&str>;
+
+    /// Identificador de glifo de la tecla para una familia de dispositivo.
+    fn key_glyph(&self, key: &K, family: DeviceFamily) -> Option<String>;
+
+    /// Nombre y glifo de una tecla.
+    ///
+    /// Las teclas sin nombre conocido se presentan como `"?"`.
+    fn key_label(&self, key: &K, family: DeviceFamily) -> KeyLabel {
+        KeyLabel {
+            name: self.key_name(key).unwrap_or("?").to_owned(),
+            glyph: self.key_glyph(key, family),
+        }
+    }
+
+    /// Presenta todas las teclas asignadas a una acción en el contexto activo,
+    /// en el orden de `MappingLayerState::bindings` (binding principal primero,
+    /// alternativos después).
+    ///
+    /// Devuelve un vector vacío si la acción no tiene teclas asignadas.
+    fn display_binding<M>(&self, mapping: &M, action: &str, family: DeviceFamily) -> Vec<KeyLabel>
+    where
+        M: MappingLayerState<KeyCode = K>,
+    {
+        labels_for(self, mapping.bindings(), action, family)
+    }
+
+    /// Igual que `display_binding`, pero en un contexto específico.
+    fn display_binding_in<M>(&self, mapping: &M, ctx: &M::Ctx, action: &str, family: DeviceFamily) -> Vec<KeyLabel>
+    where
+        M: MappingLayerState<KeyCode = K>,
+    {
+        labels_for(self, mapping.bindings_in(ctx), action, family)
+    }
+}
+
+fn labels_for<K, D>(display: &D, bindings: &[(String, K)], action: &str, family: DeviceFamily) -> Vec<KeyLabel>
+where
+    D: KeyDisplay<K> + ?Sized,
+{
+    bindings
+        .iter()
+        .filter(|(bound, _)| bound == action)
+        .map(|(_, key)| display.key_label(key, family))
+        .collect()
+}
+
+
+/// Implementación de [`KeyDisplay`] para cualquier código convertible a
+/// [`StandardKey`], con tabla de nombres intercambiable.
+///
+/// Las teclas que la tabla no cubre se resuelven con [`EnglishKeyNames`].
+#[derive(Debug, Clone, Default)]
+pub struct StandardKeyDisplay<T: KeyNameTable = EnglishKeyNames> {
+    names: T,
+}
+
+impl<T: KeyNameTable> StandardKeyDisplay<T> {
+    /// Crea un presentador con la tabla de nombres indicada.
+    pub fn new(names: T) -> Self {
+        Self { names }
+    }
+
+    /// Tabla de nombres activa.
+    pub fn names(&self) -> &T {
+        &self.names
+    }
+
+    /// Reemplaza la tabla de nombres (cambio de idioma).
+    pub fn set_names(&mut self, names: T) {
+        self.names = names;
+    }
+}
+
+impl<K: ToStandardKey, T: KeyNameTable> KeyDisplay<K> for StandardKeyDisplay<T> {
+    fn key_name(&self, key: &K) -> Option<&str> {
+        let key = key.to_standard_key()?;
+        self.names.key_name(key).or(Some(key.english_name()))
+    }
+
+    fn key_glyph(&self, key: &K, family: DeviceFamily) -> Option<String> {
+        glyph_id(key.to_standard_key()?, family)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::layers::standard_keys::KeyGroup;
+
+    const FAMILIES: [DeviceFamily; 4] =
+        [DeviceFamily::Xbox, DeviceFamily::PlayStation, DeviceFamily::Nintendo, DeviceFamily::GenericGamepad];
+
+    #[test]
+    fn face_buttons_follow_position() {
+        let glyphs: Vec<_> = FAMILIES.iter().map(|f| glyph_id(StandardKey::GamepadSouth, *f).unwrap()).collect();
+        assert_eq!(glyphs, ["xbox_a", "ps_cross", "nintendo_b", "pad_south"]);
+    }
+
+    #[test]
+    fn gamepad_axes_have_glyphs() {
+        assert_eq!(glyph_id(StandardKey::GamepadLeftStickX, DeviceFamily::Xbox).as_deref(), Some("xbox_ls_x"));
+        assert_eq!(glyph_id(StandardKey::GamepadRightStickY, DeviceFamily::PlayStation).as_deref(), Some("ps_rs_y"));
+        assert_eq!(
+            glyph_id(StandardKey::GamepadLeftStickY, DeviceFamily::GenericGamepad).as_deref(),
+            Some("pad_left_stick_y")
+        );
+        // Los ejes de gatillo comparten el glifo del gatillo.
+        for family in FAMILIES {
+            assert_eq!(
+                glyph_id(StandardKey::GamepadRightTriggerAxis, family),
+                glyph_id(StandardKey::GamepadRightTrigger, family)
+            );
+        }
+    }
+
+    #[test]
+    fn every_gamepad_key_has_a_glyph() {
+        for key in StandardKey::ALL.iter().copied().filter(|k| k.group() == KeyGroup::Gamepad) {
+            for family in FAMILIES {
+                assert!(glyph_id(key, family).is_some(), "{key:?} sin glifo para {family:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn glyphs_are_scoped_to_family() {
+        assert_eq!(glyph_id(StandardKey::Space, DeviceFamily::Keyboard).as_deref(), Some("key_space"));
+        assert_eq!(glyph_id(StandardKey::Space, DeviceFamily::Xbox), None);
+        assert_eq!(glyph_id(StandardKey::GamepadSouth, DeviceFamily::Keyboard), None);
+        assert_eq!(glyph_id(StandardKey::MouseLeft, DeviceFamily::Mouse).as_deref(), Some("mouse_left"));
+        assert_eq!(glyph_id(StandardKey::MouseWheel, DeviceFamily::Mouse).as_deref(), Some("mouse_wheel"));
+        assert_eq!(glyph_id(StandardKey::MouseLeft, DeviceFamily::Keyboard), None);
+    }
+
+    #[test]
+    fn standard_display_resolves_name_and_glyph() {
+        let display = StandardKeyDisplay::new(EnglishKeyNames);
+        let label = display.key_label(&StandardKey::GamepadLeftTriggerAxis, DeviceFamily::PlayStation);
+        assert_eq!(label, KeyLabel { name: "Left Trigger Axis".into(), glyph: Some("ps_l2".into()) });
+    }
+}
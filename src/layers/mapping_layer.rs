@@ -1,4 +1,13 @@
-use crate::layers::{history::HistoryStateExt, processing_layer::ProcessingLayerState, raw_layer::KeyCodeExt};
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use crate::layers::{
+    action_events::ActionEvent,
+    history::HistoryStateExt,
+    input_macros::InputMacro,
+    processing_layer::ProcessingLayerState,
+    raw_layer::{KeyCodeExt, KeyEventExt},
+};
 
 
 // -----------------------------------------------------------------------------
@@ -19,6 +28,117 @@ use crate::layers::{history::HistoryStateExt, processing_layer::ProcessingLayerS
 pub trait ContextId: Clone + Eq {}
 
 
+// -----------------------------------------------------------------------------
+// BindingLayer / BindingOverride
+// -----------------------------------------------------------------------------
+
+/// Capa de origen de un binding dentro del sistema de mapeo.
+///
+/// Los datos de mapeo se dividen en dos capas:
+/// - **Default:** bindings enviados con el juego. Inmutables en runtime;
+///   solo cambian al importar un nuevo conjunto de defaults (p. ej. un parche).
+/// - **User:** overrides del jugador. Es la única capa que tocan las
+///   operaciones de mapeo, reset y export.
+///
+/// Las consultas resuelven siempre `User → Default`: si existe un override
+/// se usa, si no, se cae al binding por defecto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingLayer {
+    /// Binding enviado con el juego.
+    Default,
+    /// Override definido por el usuario.
+    User,
+}
+
+/// Override de usuario sobre un binding por defecto.
+///
+/// Un override puede reasignar la acción a otra tecla o dejarla
+/// explícitamente sin tecla, aunque el default sí tenga una.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingOverride<K> {
+    /// La acción se reasigna a la tecla indicada.
+    Mapped(K),
+    /// La acción queda explícitamente sin asignar.
+    Unmapped,
+}
+
+
+// -----------------------------------------------------------------------------
+// KeyMatchMode
+// -----------------------------------------------------------------------------
+
+/// Modo en que un binding compara su tecla con los eventos de entrada.
+///
+/// - **Physical:** por posición. `WASD` sigue siendo `WASD` en AZERTY
+///   (donde está rotulado `ZQSD`). Recomendado para movimiento.
+/// - **Logical:** por símbolo. "Pulsa I para inventario" sigue a la tecla
+///   rotulada `I`, esté donde esté. Recomendado para atajos mnemónicos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyMatchMode {
+    /// Compara con `KeyEventExt::physical_key`.
+    #[default]
+    Physical,
+    /// Compara con `KeyEventExt::logical_key`.
+    Logical,
+}
+
+
+// -----------------------------------------------------------------------------
+// HoldMode
+// -----------------------------------------------------------------------------
+
+/// Semántica de mantenimiento de una acción (opción de accesibilidad).
+///
+/// - **Hold:** la acción está activa mientras la tecla se mantiene.
+/// - **Toggle:** una pulsación activa la acción y la siguiente la desactiva,
+///   sin necesidad de mantener la tecla.
+///
+/// La conversión es transparente para el gameplay: `GameContract::action_held`
+/// refleja el estado conmutado, y `action_pressed` / `action_released` se
+/// emiten al activar y desactivar respectivamente.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HoldMode {
+    /// Activa mientras se mantiene la tecla.
+    #[default]
+    Hold,
+    /// Cada pulsación alterna entre activa e inactiva.
+    Toggle,
+}
+
+
+// -----------------------------------------------------------------------------
+// ScanConfig
+// -----------------------------------------------------------------------------
+
+/// Configuración del modo de **escaneo** (acceso por un único pulsador).
+///
+/// En modo escaneo, la capa de mapeo recorre cíclicamente las acciones del
+/// contexto activo (`actions_in(current_context)`), resaltando una cada
+/// `interval`. Al pulsar `switch`, la acción resaltada se dispara como si su
+/// tecla se hubiese pulsado: genera `action_pressed`, `action_held` durante
+/// `press_duration`, `action_released` y los eventos de acción
+/// correspondientes.
+///
+/// Mientras el escaneo está activo, el resto de bindings del contexto
+/// siguen funcionando con normalidad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanConfig<K> {
+    /// Tecla o botón que actúa como pulsador.
+    pub switch: K,
+    /// Tiempo que cada acción permanece resaltada.
+    pub interval: Duration,
+    /// Tiempo que la acción disparada permanece activa (`held`).
+    pub press_duration: Duration,
+    /// Acciones del contexto que el escaneo omite (p. ej. el propio menú).
+    pub skip_actions: Vec<String>,
+    /// Número de vueltas completas sin pulsación antes de pausar el escaneo.
+    ///
+    /// `None` escanea indefinidamente. Una pulsación reanuda el escaneo
+    /// pausado sin disparar ninguna acción.
+    pub max_cycles: Option<u32>,
+}
+
+
 // -----------------------------------------------------------------------------
 // MappingLayerState — SOLO LECTURA, CERO ALOCACIONES EXTRAS
 // -----------------------------------------------------------------------------
@@ -32,9 +152,14 @@ pub trait ContextId: Clone + Eq {}
 /// - **no muta estructuras**.
 ///
 /// Exposición principal: *slices* y referencias para máximo rendimiento.
+///
+/// Todas las consultas de bindings (`key_for_action`, `actions_for_key`,
+/// `bindings`, ...) devuelven el resultado **efectivo**, resuelto
+/// `User → Default` (ver [`BindingLayer`]).
 pub trait MappingLayerState {
     type KeyCode: KeyCodeExt;
     type Ctx: ContextId;
+    type DeviceKind: Clone;
 
 
     // -------------------------------------------------------------------------
@@ -126,11 +251,165 @@ pub trait MappingLayerState {
     fn is_context_enabled(&self, ctx : &Self::Ctx) -> bool;
 
 
+    // -------------------------------------------------------------------------
+    // DEFAULTS / USER OVERRIDES
+    // -------------------------------------------------------------------------
+
+    /// Devuelve la tecla por defecto (capa `Default`) de una acción en un
+    /// contexto específico, ignorando cualquier override del usuario.
+    fn default_key_for_action_in(&self, ctx: &Self::Ctx, action: &str) -> Option<Self::KeyCode>;
+
+    /// Devuelve el override del usuario para una acción en un contexto específico.
+    ///
+    /// Retorna `None` si la acción usa su binding por defecto.
+    fn user_override_in(&self, ctx: &Self::Ctx, action: &str) -> Option<&BindingOverride<Self::KeyCode>>;
+
+    /// Indica de qué capa proviene el binding efectivo de una acción.
+    ///
+    /// Retorna `None` si el contexto o la acción no existen.
+    fn binding_layer_in(&self, ctx: &Self::Ctx, action: &str) -> Option<BindingLayer>;
+
+    /// Devuelve los bindings por defecto de un contexto específico.
+    fn default_bindings_in(&self, ctx: &Self::Ctx) -> &[(String, Self::KeyCode)];
+
+    /// Devuelve únicamente los overrides del usuario en un contexto específico.
+    ///
+    /// Es exactamente la información que persiste `export_key_mappings`.
+    fn user_overrides_in(&self, ctx: &Self::Ctx) -> &[(String, BindingOverride<Self::KeyCode>)];
+
+    /// Indica si existe al menos un override del usuario en cualquier contexto.
+    fn has_user_overrides(&self) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // KEY MATCH MODE
+    // -------------------------------------------------------------------------
+
+    /// Modo de comparación (físico o lógico) del binding de una acción
+    /// en el contexto activo.
+    fn key_match_mode(&self, action: &str) -> KeyMatchMode;
+
+    /// Igual que `key_match_mode`, pero en un contexto específico.
+    fn key_match_mode_in(&self, ctx: &Self::Ctx, action: &str) -> KeyMatchMode;
+
+
+    // -------------------------------------------------------------------------
+    // HOLD / TOGGLE
+    // -------------------------------------------------------------------------
+
+    /// Semántica de mantenimiento de una acción en el contexto activo.
+    fn hold_mode(&self, action: &str) -> HoldMode;
+
+    /// Igual que `hold_mode`, pero en un contexto específico.
+    fn hold_mode_in(&self, ctx: &Self::Ctx, action: &str) -> HoldMode;
+
+    /// Indica si una acción en modo `Toggle` está actualmente activada.
+    ///
+    /// Siempre `false` para acciones en modo `Hold`.
+    fn is_toggled_on(&self, action: &str) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // SCANNING (ONE-SWITCH)
+    // -------------------------------------------------------------------------
+
+    /// Configuración del modo escaneo, si está activo.
+    fn scan_config(&self) -> Option<&ScanConfig<Self::KeyCode>>;
+
+    /// Acción resaltada actualmente por el escaneo.
+    ///
+    /// Retorna `None` si el escaneo está inactivo o pausado, o si el
+    /// contexto activo no tiene acciones escaneables.
+    fn scan_highlighted_action(&self) -> Option<&str>;
+
+    /// Indica si el escaneo está pausado tras agotar `max_cycles`.
+    fn is_scan_paused(&self) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // TEXT FOCUS
+    // -------------------------------------------------------------------------
+
+    /// Indica si el modo *text focus* está activo.
+    ///
+    /// Mientras está activo, las pulsaciones de teclado alimentan solo el
+    /// canal de texto y no disparan acciones de gameplay, salvo las acciones
+    /// permitidas explícitamente (ver `is_action_allowed_in_text_focus`).
+    fn has_text_focus(&self) -> bool;
+
+    /// Indica si una acción del contexto activo sigue resolviéndose
+    /// durante el modo *text focus* (p. ej. `"ui_cancel"`, `"ui_submit"`).
+    fn is_action_allowed_in_text_focus(&self, action: &str) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // MACROS / TURBO
+    // -------------------------------------------------------------------------
+
+    /// Macros definidas en el contexto activo.
+    fn macros(&self) -> &[InputMacro<Self::KeyCode>];
+
+    /// Macros definidas en un contexto específico.
+    fn macros_in(&self, ctx: &Self::Ctx) -> &[InputMacro<Self::KeyCode>];
+
+    /// Intervalo de turbo de una tecla, si tiene turbo activado.
+    ///
+    /// Mientras la tecla se mantiene, el motor de macros la repite
+    /// (liberación + pulsación) cada `interval`.
+    fn turbo_interval(&self, key: &Self::KeyCode) -> Option<Duration>;
+
+
+    // -------------------------------------------------------------------------
+    // INPUT BUFFERING
+    // -------------------------------------------------------------------------
+
+    /// Ventana de buffer de una acción en el contexto activo.
+    ///
+    /// Una pulsación ocurrida dentro de esta ventana sigue disponible para
+    /// `GameContract::consume_buffered` aunque el juego no la haya leído en
+    /// su frame (p. ej. saltar 100 ms antes de aterrizar).
+    ///
+    /// Retorna `None` si la acción no usa buffer.
+    fn buffer_window(&self, action: &str) -> Option<Duration>;
+
+    /// Igual que `buffer_window`, pero en un contexto específico.
+    fn buffer_window_in(&self, ctx: &Self::Ctx, action: &str) -> Option<Duration>;
+
+
+    // -------------------------------------------------------------------------
+    // CONTROL SCHEME
+    // -------------------------------------------------------------------------
+
+    /// Nombre del esquema de control aplicado actualmente
+    /// (p. ej. `"Classic"`, `"Left-handed"`).
+    ///
+    /// Retorna `None` si la configuración actual no proviene de ningún
+    /// esquema guardado. Lo establece el gestor de perfiles al cambiar
+    /// de esquema (ver [`crate::layers::profiles`]).
+    fn active_scheme(&self) -> Option<&str>;
+
+
+    // -------------------------------------------------------------------------
+    // ACTION EVENTS
+    // -------------------------------------------------------------------------
+
+    /// Devuelve los eventos de acción generados en el frame actual,
+    /// en orden cronológico.
+    ///
+    /// Se llena en `resolve_actions` y se vacía en `begin_frame` o con
+    /// `MappingLayerControl::drain_action_events`.
+    fn action_events(&self) -> &[ActionEvent<Self::DeviceKind>];
+
+
     // -------------------------------------------------------------------------
     // EXPORT (opcional)
     // -------------------------------------------------------------------------
 
-    /// Exporta toda la configuración en formato serializable.
+    /// Exporta la configuración del usuario en formato serializable.
+    ///
+    /// Solo se exporta la capa `User` (el *diff* respecto a los defaults).
+    /// Así, si una actualización del juego cambia un default que el jugador
+    /// no tocó, el nuevo default se aplica al importar.
     ///
     /// Disponible solo con la feature `IE_maping`.
     #[cfg(feature = "IE_maping")]
@@ -154,9 +433,14 @@ pub trait MappingLayerState {
 ///
 /// Esta capa es mutante y debe ser usada por el editor y por scripts de tooling.
 /// El runtime del juego normalmente usará solo `MappingLayerState`.
+///
+/// Las operaciones de mapeo (`map_action*`, `unmap_action*`) escriben
+/// siempre en la capa `User`; la capa `Default` solo se reemplaza con
+/// `import_default_mappings`.
 pub trait MappingLayerControl {
     type KeyCode: KeyCodeExt;
     type Ctx: ContextId;
+    type DeviceKind: Clone;
 
     // -------------------------------------------------------------------------
     // CONTEXT MANAGEMENT
@@ -169,7 +453,8 @@ pub trait MappingLayerControl {
     /// - `false` si el contexto no existe.
     ///
     /// Cambiar el contexto afecta qué acciones y bindings estarán vigentes
-    /// durante la resolución del frame.
+    /// durante la resolución del frame. Las acciones en modo `Toggle` del
+    /// contexto anterior que queden fuera de resolución se liberan.
     fn set_current_context(&mut self, ctx: Self::Ctx) -> bool;
 
 
@@ -326,17 +611,25 @@ pub trait MappingLayerControl {
     // CONTEXT RESET
     // -------------------------------------------------------------------------
 
-    /// Resetea el contexto activo a estado vacío:
-    /// - elimina **todas** las asignaciones,
-    /// - conserva las acciones pero sin teclas asociadas.
+    /// Resetea el contexto activo a sus bindings por defecto:
+    /// - elimina **todos** los overrides del usuario,
+    /// - conserva la capa `Default` intacta.
+    ///
+    /// Retorna `true` si el contexto tenía overrides.
     fn reset_context(&mut self) -> bool;
 
-    /// Resetea un contexto específico a estado vacío.
+    /// Resetea un contexto específico a sus bindings por defecto.
     fn reset_context_in(&mut self, ctx: &Self::Ctx) -> bool;
 
-    /// Resetea **todos** los contextos del sistema.
+    /// Resetea **todos** los contextos del sistema a sus bindings por defecto.
     fn reset_all_contexts(&mut self);
 
+    /// Elimina el override del usuario de una sola acción, devolviéndola
+    /// a su binding por defecto.
+    ///
+    /// Retorna `true` si la acción tenía un override.
+    fn reset_action_in(&mut self, ctx: &Self::Ctx, action: &str) -> bool;
+
 
     // -------------------------------------------------------------------------
     // CONTEXT ENABLE / DISABLE
@@ -353,19 +646,152 @@ pub trait MappingLayerControl {
     /// Un contexto deshabilitado:
     /// - no participa en resolución,
     /// - no puede ser activado como actual.
+    ///
+    /// Las acciones en modo `Toggle` activadas en ese contexto se liberan
+    /// automáticamente en la siguiente resolución (emitiendo `Canceled`).
     fn disable_context(&mut self, ctx: &Self::Ctx) -> bool;
 
 
+    // -------------------------------------------------------------------------
+    // KEY MATCH MODE
+    // -------------------------------------------------------------------------
+
+    /// Establece si el binding de una acción del contexto activo se compara
+    /// por posición física o por símbolo lógico.
+    ///
+    /// Retorna `false` si la acción no existe. Forma parte de la capa `User`.
+    fn set_key_match_mode(&mut self, action: &str, mode: KeyMatchMode) -> bool;
+
+    /// Igual que `set_key_match_mode`, pero en un contexto específico.
+    fn set_key_match_mode_in(&mut self, ctx: &Self::Ctx, action: &str, mode: KeyMatchMode) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // HOLD / TOGGLE
+    // -------------------------------------------------------------------------
+
+    /// Establece la semántica de mantenimiento de una acción del contexto activo.
+    ///
+    /// Retorna `false` si la acción no existe. Forma parte de la capa `User`,
+    /// por lo que se guarda por perfil y esquema junto con los bindings.
+    ///
+    /// Cambiar de `Toggle` a `Hold` con la acción activada la libera en la
+    /// siguiente resolución.
+    fn set_hold_mode(&mut self, action: &str, mode: HoldMode) -> bool;
+
+    /// Igual que `set_hold_mode`, pero en un contexto específico.
+    fn set_hold_mode_in(&mut self, ctx: &Self::Ctx, action: &str, mode: HoldMode) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // SCANNING (ONE-SWITCH)
+    // -------------------------------------------------------------------------
+
+    /// Activa (`Some`) o desactiva (`None`) el modo escaneo.
+    ///
+    /// Al activarlo, el resaltado empieza en la primera acción escaneable
+    /// del contexto activo. Al cambiar de contexto, el escaneo se reinicia
+    /// sobre las acciones del nuevo contexto.
+    ///
+    /// El avance del resaltado y el disparo se calculan en `resolve_actions`
+    /// con los timestamps de `HistoryStateExt`, por lo que las acciones
+    /// disparadas llegan al juego por el camino normal de `GameContract`.
+    fn set_scan_mode(&mut self, config: Option<ScanConfig<Self::KeyCode>>);
+
+
+    // -------------------------------------------------------------------------
+    // TEXT FOCUS
+    // -------------------------------------------------------------------------
+
+    /// Activa o desactiva el modo *text focus* (chat, introducir nombre...).
+    ///
+    /// Al activarlo, las acciones mantenidas por teclas de teclado se
+    /// liberan en la siguiente resolución, emitiendo `Canceled`.
+    fn set_text_focus(&mut self, focused: bool);
+
+    /// Permite (o deja de permitir) que una acción del contexto activo se
+    /// resuelva durante el modo *text focus*.
+    ///
+    /// Retorna `false` si la acción no existe.
+    fn allow_action_in_text_focus(&mut self, action: &str, allow: bool) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // MACROS / TURBO
+    // -------------------------------------------------------------------------
+
+    /// Añade o reemplaza (por nombre) una macro en el contexto activo.
+    ///
+    /// Retorna `false` si la acción disparadora no existe. Forma parte de la
+    /// capa `User` y se exporta con `export_key_mappings`.
+    fn set_macro(&mut self, input_macro: InputMacro<Self::KeyCode>) -> bool;
+
+    /// Igual que `set_macro`, pero en un contexto específico.
+    fn set_macro_in(&mut self, ctx: &Self::Ctx, input_macro: InputMacro<Self::KeyCode>) -> bool;
+
+    /// Elimina una macro del contexto activo.
+    fn remove_macro(&mut self, name: &str) -> bool;
+
+    /// Elimina una macro de un contexto específico.
+    fn remove_macro_in(&mut self, ctx: &Self::Ctx, name: &str) -> bool;
+
+    /// Activa (`Some(interval)`) o desactiva (`None`) el turbo de una tecla.
+    ///
+    /// Forma parte de la capa `User`.
+    fn set_turbo(&mut self, key: Self::KeyCode, interval: Option<Duration>);
+
+
+    // -------------------------------------------------------------------------
+    // INPUT BUFFERING
+    // -------------------------------------------------------------------------
+
+    /// Establece la ventana de buffer de una acción en el contexto activo.
+    ///
+    /// `None` desactiva el buffer. Retorna `false` si la acción no existe.
+    ///
+    /// La ventana se mide con los timestamps de `HistoryStateExt`, no con
+    /// frames, por lo que es independiente del framerate.
+    fn set_buffer_window(&mut self, action: &str, window: Option<Duration>) -> bool;
+
+    /// Igual que `set_buffer_window`, pero en un contexto específico.
+    fn set_buffer_window_in(&mut self, ctx: &Self::Ctx, action: &str, window: Option<Duration>) -> bool;
+
+
+    // -------------------------------------------------------------------------
+    // CONTROL SCHEME
+    // -------------------------------------------------------------------------
+
+    /// Marca el nombre del esquema de control aplicado actualmente.
+    ///
+    /// Solo actualiza la etiqueta expuesta por `MappingLayerState::active_scheme`;
+    /// no modifica bindings. Normalmente lo invoca el gestor de perfiles
+    /// justo después de aplicar un esquema.
+    fn set_active_scheme(&mut self, scheme: Option<&str>);
+
+
     // -------------------------------------------------------------------------
     // IMPORT (opcional)
     // -------------------------------------------------------------------------
 
-    /// Importa una configuración serializable de mapeo.
+    /// Importa una configuración serializable de mapeo del usuario.
+    ///
+    /// Reemplaza la capa `User` completa; la capa `Default` no se modifica.
+    /// Los overrides de acciones o contextos inexistentes se descartan.
     ///
     /// Disponible solo bajo la feature `IE_maping`.
     #[cfg(feature = "IE_maping")]
     fn import_key_mappings<T>(&mut self, data: T);
 
+    /// Importa la capa `Default` (bindings enviados con el juego).
+    ///
+    /// Reemplaza los defaults sin tocar los overrides del usuario, de modo
+    /// que los cambios de una actualización lleguen a todas las acciones
+    /// que el jugador no personalizó.
+    ///
+    /// Disponible solo bajo la feature `IE_maping`.
+    #[cfg(feature = "IE_maping")]
+    fn import_default_mappings<T>(&mut self, data: T);
+
     // -------------------------------------------------------------------------
     // FRAME CYCLE — Agregado según solicitaste
     // -------------------------------------------------------------------------
@@ -374,6 +800,7 @@ pub trait MappingLayerControl {
     ///
     /// - Limpia el estado final del frame anterior.
     /// - Reinicia buffers internos.
+    /// - Vacía la cola de eventos de acción no drenados.
     /// - No se resuelven acciones todavía.
     fn begin_frame(&mut self);
 
@@ -388,7 +815,42 @@ pub trait MappingLayerControl {
     /// - released(action)
     /// - held(action)
     /// - action_value(action)
-    fn resolve_actions(&mut self,processing: &impl ProcessingLayerState<KeyCode = Self::KeyCode>,history: &impl HistoryStateExt<KeyCode = Self::KeyCode>);
+    /// - la cola ordenada de eventos de acción, a partir de
+    ///   `ProcessingLayerState::frame_events`.
+    /// - las pulsaciones buffereadas, comparando `HistoryStateExt::last_event_time`
+    ///   con `HistoryStateExt::current_time` y la ventana de cada acción.
+    ///
+    /// Cada binding se evalúa con las consultas físicas (`is_pressed`...) o
+    /// lógicas (`is_logical_pressed`...) según su `KeyMatchMode`.
+    fn resolve_actions(
+        &mut self,
+        processing: &impl ProcessingLayerState<KeyCode = Self::KeyCode, Event: KeyEventExt<DeviceKind = Self::DeviceKind>>,
+        history: &impl HistoryStateExt<KeyCode = Self::KeyCode>,
+    );
+
+    /// Dispara una acción del contexto activo durante `frames` frames,
+    /// **sin pasar por sus bindings**.
+    ///
+    /// La acción se presiona en la siguiente resolución, se mantiene
+    /// `frames` frames y se libera después. Sus eventos de acción se marcan
+    /// con `InputOrigin::Synthetic`.
+    ///
+    /// Retorna `false` si la acción no existe o `frames` es 0.
+    fn inject_action(&mut self, action: &str, frames: u32) -> bool;
+
+    /// Extrae todos los eventos de acción pendientes del frame,
+    /// en orden cronológico, dejando la cola vacía.
+    fn drain_action_events(&mut self) -> Vec<ActionEvent<Self::DeviceKind>>;
+
+    /// Igual que `drain_action_events`, pero añade los eventos al final de
+    /// `out`.
+    ///
+    /// La implementación por defecto delega en `drain_action_events` y
+    /// asigna un vector por llamada; las implementaciones que quieran un
+    /// frame sin asignaciones (reutilizando `out`) deben sobrescribirla.
+    fn drain_action_events_into(&mut self, out: &mut Vec<ActionEvent<Self::DeviceKind>>) {
+        out.append(&mut self.drain_action_events());
+    }
 
     /// Finalización del frame:
     /// - Sella los resultados para lectura (`MappingLayerState`)
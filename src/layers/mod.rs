@@ -86,7 +86,8 @@
 //! - **contextos** (“Gameplay”, “UI”, “Vehicle”…),
 //! - **mapeos dinámicos** entre teclas y acciones,
 //! - **habilitación o deshabilitación** de contextos,
-//! - **renombramiento, clonación y reseteo** de configuraciones.
+//! - **renombramiento, clonación y reseteo** de configuraciones,
+//! - **bindings por defecto y overrides del usuario** (el reseteo vuelve a los defaults).
 //!
 //! Como en la capa procesada, esta capa también distingue entre:
 //!
@@ -111,12 +112,41 @@
 //! - [`processing_layer`] — Estado procesado del frame y control mutante del procesamiento.
 //! - [`mapping_layer`] — Acciones, contextos y mapeos.
 //! - [`history`] — Herramientas para historial y análisis temporal del input.
+//! - [`profiles`] — Perfiles de usuario y esquemas de control con nombre.
+//! - [`action_events`] — Cola ordenada de eventos de acción del frame.
+//! - [`standard_keys`] — Conjunto estándar de teclas y botones.
+//! - [`key_display`] — Nombres legibles y glifos de teclas.
+//! - [`controls_sheet`] — Hojas de controles generadas desde el mapeo.
+//! - [`filters`] — Filtros de entrada previos al procesamiento (accesibilidad).
+//! - [`input_macros`] — Macros de entrada y turbo.
+//! - [`timestamp`] — Marca de tiempo portable (`no_std`) de los eventos.
 //!
 
-//! Cada módulo contiene exclusivamente **definiciones de traits**.  
-//! No se incluye ninguna implementación real dentro de este crate.
+//! Cada módulo contiene principalmente **definiciones de traits**.  
+//! No se incluye ninguna implementación real de capas dentro de este crate;
+//! solo tipos de datos y tablas de referencia compartidas (como el conjunto
+//! estándar de teclas y sus nombres en inglés).
 
+#[cfg(feature = "alloc")]
 pub mod raw_layer;
+#[cfg(feature = "alloc")]
 pub mod mapping_layer;
+#[cfg(feature = "alloc")]
 pub mod processing_layer;
+#[cfg(feature = "alloc")]
 pub mod history;
+#[cfg(feature = "alloc")]
+pub mod profiles;
+#[cfg(feature = "alloc")]
+pub mod action_events;
+#[cfg(feature = "alloc")]
+pub mod standard_keys;
+#[cfg(feature = "alloc")]
+pub mod key_display;
+#[cfg(feature = "alloc")]
+pub mod controls_sheet;
+#[cfg(feature = "alloc")]
+pub mod filters;
+#[cfg(feature = "alloc")]
+pub mod input_macros;
+pub mod timestamp;
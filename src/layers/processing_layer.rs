@@ -9,6 +9,8 @@
 //! - teclas liberadas
 //! - transiciones (`just_pressed`, `just_released`)
 //! - duración de presión (`pressed_duration`)
+//! - repeticiones de tecla (`just_repeated`)
+//! - texto del frame y composición IME (`frame_text`, `preedit`)
 //!
 //! **No** almacena historial completo, **no** detecta combos temporales grandes,
 //! **no** interpreta gestos complejos. Es una capa *inmediata*
@@ -22,8 +24,113 @@
 //!
 //! ---------------------------------------------------------------------------
 
-use std::time::Duration;
-use crate::layers::raw_layer::{KeyCodeExt, KeyEventExt, KeyStateExt};
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::time::Duration;
+
+use super::timestamp::Timestamp;
+use crate::layers::raw_layer::{KeyCodeExt, KeyEventExt, KeyStateExt, RawSignal, TextInputEvent};
+
+
+/// ---------------------------------------------------------------------------
+/// Origen de las **repeticiones de tecla** (auto-repeat).
+///
+/// Las repeticiones nunca generan `just_pressed`; se consultan con
+/// `ProcessingLayerState::just_repeated`. Así los campos de texto pueden
+/// usarlas mientras el gameplay las ignora.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyRepeatMode {
+    /// No se reporta ninguna repetición.
+    Disabled,
+    /// Se usan las repeticiones del backend (`KeyEventExt::is_repeat`).
+    ///
+    /// El timing depende del sistema operativo y puede variar entre backends.
+    Backend,
+    /// Se ignoran las repeticiones del backend y se generan por software
+    /// con la configuración indicada, idéntica en todos los backends.
+    Software(KeyRepeatConfig),
+}
+
+/// Configuración del generador de repeticiones por software.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyRepeatConfig {
+    /// Tiempo mantenido antes de la primera repetición.
+    pub delay: Duration,
+    /// Intervalo entre repeticiones sucesivas (inverso de la tasa).
+    ///
+    /// Un intervalo nulo produce una única repetición tras `delay`.
+    pub interval: Duration,
+}
+
+impl KeyRepeatConfig {
+    /// Número de repeticiones que deben emitirse cuando el tiempo mantenido
+    /// de una tecla avanza de `held_before` a `held_now`.
+    ///
+    /// Las repeticiones ocurren en `delay`, `delay + interval`,
+    /// `delay + 2·interval`, ... Es una función pura del tiempo mantenido,
+    /// por lo que el resultado no depende del framerate ni del backend.
+    pub fn repeats_between(&self, held_before: Duration, held_now: Duration) -> u32 {
+        self.repeats_until(held_now).saturating_sub(self.repeats_until(held_before))
+    }
+
+    /// Repeticiones acumuladas tras mantener una tecla durante `held`.
+    fn repeats_until(&self, held: Duration) -> u32 {
+        if held < self.delay {
+            return 0;
+        }
+        if self.interval.is_zero() {
+            return 1;
+        }
+        let since_first = (held - self.delay).as_nanos() / self.interval.as_nanos();
+        u32::try_from(since_first).unwrap_or(u32::MAX).saturating_add(1)
+    }
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Origen de una entrada: un dispositivo real o el propio motor.
+///
+/// Permite a diagnósticos, replays y anti-trampas distinguir la entrada
+/// del jugador de la generada por macros, bots, tests o tutoriales.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InputOrigin {
+    /// Evento recibido de la Raw Layer.
+    #[default]
+    Device,
+    /// Evento inyectado por el motor (macros, turbo, inyección programática).
+    Synthetic,
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Evento de tecla **sintético**, generado por el propio motor
+/// (macros, turbo, inyección) y no por un dispositivo.
+///
+/// No requiere un tipo de evento del backend: basta con la tecla, el
+/// sentido de la transición y el instante.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticKeyEvent<K> {
+    /// Tecla afectada.
+    pub key: K,
+    /// `true` para pulsación, `false` para liberación.
+    pub pressed: bool,
+    /// Instante programado del evento.
+    pub timestamp: Timestamp,
+}
+
+/// Evento de eje **sintético**: fija el valor analógico de una entrada.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticAxisEvent<K> {
+    /// Entrada analógica afectada.
+    pub key: K,
+    /// Nuevo valor del eje.
+    pub value: f32,
+    /// Instante programado del evento.
+    pub timestamp: Timestamp,
+}
 
 
 /// ---------------------------------------------------------------------------
@@ -49,7 +156,8 @@ pub trait ProcessingLayerControl {
     /// - timers de presión/liberación
     /// - cualquier estructura intermedia
     ///
-    /// Útil al cambiar de escena, reiniciar niveles, o restaurar foco de ventana.
+    /// Útil al cambiar de escena o reiniciar niveles. Para pérdida de foco
+    /// usar `handle_signal`, que además genera las liberaciones pendientes.
     fn reset(&mut self);
 
     /// Procesa los eventos crudos acumulados del frame.
@@ -62,6 +170,55 @@ pub trait ProcessingLayerControl {
     /// *Importante:* no debe mover `now → prev`, eso se hace en `begin_frame`.
     fn update(&mut self, events: &[Self::Event]);
 
+    /// Procesa eventos sintéticos (macros, turbo, inyección) como si fueran
+    /// eventos crudos del frame.
+    ///
+    /// Se intercalan con los eventos de `update` por timestamp, y el estado
+    /// resultante queda marcado con `InputOrigin::Synthetic`.
+    fn inject_synthetic(&mut self, events: &[SyntheticKeyEvent<<Self::Event as KeyEventExt>::KeyCode>]);
+
+    /// Procesa eventos de eje sintéticos.
+    ///
+    /// El valor se mantiene hasta el siguiente evento (real o sintético)
+    /// de la misma entrada.
+    fn inject_synthetic_axis(&mut self, events: &[SyntheticAxisEvent<<Self::Event as KeyEventExt>::KeyCode>]);
+
+    /// Procesa una señal de foco o suspensión de la Raw Layer.
+    ///
+    /// - `FocusLost` / `Suspended`: sintetiza una liberación para cada tecla
+    ///   mantenida (generando `just_released` en este frame), detiene los
+    ///   timers de `pressed_duration` y activa el flag de reconciliación.
+    /// - `FocusGained` / `Resumed`: mantiene el flag de reconciliación hasta
+    ///   el final del primer frame completo con foco.
+    ///
+    /// Mientras se reconcilia, si `set_suppress_held_on_focus(true)` está
+    /// activo, las teclas que ya estaban presionadas al volver el foco no
+    /// generan `just_pressed`; solo cuentan tras soltarse y volver a pulsarse.
+    fn handle_signal(&mut self, signal: RawSignal);
+
+    /// Procesa los eventos del canal de texto del frame.
+    ///
+    /// - `Commit`: se añade al texto del frame (`frame_text`).
+    /// - `Preedit`: reemplaza la composición actual (`preedit`).
+    /// - `PreeditCleared`: elimina la composición actual.
+    ///
+    /// El texto del frame se vacía en `begin_frame`; la composición persiste
+    /// entre frames hasta que el IME la reemplace o la cierre.
+    fn update_text(&mut self, events: &[TextInputEvent]);
+
+    /// Configura si se suprime `just_pressed` para las teclas que siguen
+    /// presionadas al recuperar el foco.
+    ///
+    /// Por defecto, las implementaciones deberían suprimirlo (`true`).
+    fn set_suppress_held_on_focus(&mut self, suppress: bool);
+
+    /// Configura el origen de las repeticiones de tecla.
+    ///
+    /// Con `KeyRepeatMode::Software`, la implementación debe calcular las
+    /// repeticiones de cada frame con `KeyRepeatConfig::repeats_between`
+    /// a partir de `pressed_duration`.
+    fn set_repeat_mode(&mut self, mode: KeyRepeatMode);
+
 
     /// Debe ser llamado al inicio del frame.
     ///
@@ -113,6 +270,7 @@ pub trait ProcessingLayerControl {
 pub trait ProcessingLayerState {
     type KeyCode: KeyCodeExt;
     type KeyState: KeyStateExt;
+    type Event: KeyEventExt;
 
     // -----------------------------------------------------------------------
     // ESTADO INMEDIATO
@@ -132,22 +290,102 @@ pub trait ProcessingLayerState {
     /// Devuelve el estado lógico actual de la tecla.
     fn get_key_state(&self, key: &Self::KeyCode) -> Self::KeyState;
 
+    /// Valor analógico actual de la entrada (`KeyEventExt::axis_value`).
+    ///
+    /// Las entradas digitales devuelven `1.0` presionadas y `0.0` liberadas.
+    fn axis_value(&self, key: &Self::KeyCode) -> f32;
+
+    /// Origen del último evento que modificó la tecla.
+    ///
+    /// Retorna `None` si la tecla no tiene eventos registrados.
+    fn key_origin(&self, key: &Self::KeyCode) -> Option<InputOrigin>;
+
+    // -----------------------------------------------------------------------
+    // TECLAS LÓGICAS
+    // -----------------------------------------------------------------------
+    //
+    // El resto de consultas de este trait usan el identificador físico
+    // (`KeyEventExt::physical_key`). Estas variantes usan el lógico
+    // (`KeyEventExt::logical_key`), necesario para bindings por símbolo.
+
+    /// Devuelve `true` si alguna tecla presionada produce el símbolo lógico `key`.
+    fn is_logical_pressed(&self, key: &Self::KeyCode) -> bool;
+
+    /// `true` si una tecla que produce el símbolo lógico `key` fue presionada
+    /// *en este frame*.
+    fn logical_just_pressed(&self, key: &Self::KeyCode) -> bool;
+
+    /// `true` si una tecla que produce el símbolo lógico `key` fue liberada
+    /// *en este frame*.
+    fn logical_just_released(&self, key: &Self::KeyCode) -> bool;
+
+    // -----------------------------------------------------------------------
+    // FOCO / SUSPENSIÓN
+    // -----------------------------------------------------------------------
+
+    /// Devuelve `true` si la aplicación tiene foco de entrada y no está suspendida.
+    fn has_focus(&self) -> bool;
+
+    /// Devuelve `true` mientras el estado se está reconciliando tras una
+    /// pérdida de foco o suspensión.
+    ///
+    /// Durante la reconciliación el estado de teclas puede no coincidir con
+    /// el hardware real; el gameplay puede usar este flag para ignorar
+    /// entradas ambiguas.
+    fn is_reconciling(&self) -> bool;
+
     // -----------------------------------------------------------------------
     // TRANSICIONES
     // -----------------------------------------------------------------------
 
     /// `true` si la tecla fue presionada *en este frame*.
+    ///
+    /// Las auto-repeticiones (`KeyEventExt::is_repeat`) no cuentan.
     fn just_pressed(&self, key: &Self::KeyCode) -> bool;
 
     /// `true` si la tecla fue liberada *en este frame*.
     fn just_released(&self, key: &Self::KeyCode) -> bool;
 
+    /// `true` si la tecla generó al menos una repetición *en este frame*.
+    ///
+    /// Nunca es `true` en el mismo frame que `just_pressed` para la misma
+    /// pulsación. Depende del `KeyRepeatMode` configurado.
+    fn just_repeated(&self, key: &Self::KeyCode) -> bool;
+
+    /// Número de repeticiones de la tecla generadas en este frame.
+    ///
+    /// Puede ser mayor que 1 si la tasa de repetición supera el framerate.
+    fn repeat_count(&self, key: &Self::KeyCode) -> u32;
+
     /// Devuelve `true` si *alguna* tecla fue presionada en este frame.
+    ///
+    /// Las repeticiones no cuentan como pulsaciones.
     fn any_key_just_pressed(&self) -> bool;
 
     /// Devuelve `true` si *alguna* tecla fue liberada en este frame.
     fn any_key_just_released(&self) -> bool;
 
+    /// Devuelve los eventos crudos recibidos en este frame, en orden cronológico.
+    ///
+    /// Permite a la capa de mapeo generar eventos de acción con precisión
+    /// sub-frame (timestamp y dispositivo de origen).
+    fn frame_events(&self) -> &[Self::Event];
+
+    // -----------------------------------------------------------------------
+    // TEXTO / IME
+    // -----------------------------------------------------------------------
+
+    /// Texto confirmado durante este frame, concatenado en orden.
+    ///
+    /// Cadena vacía si no hubo entrada de texto.
+    fn frame_text(&self) -> &str;
+
+    /// Texto en composición del IME, si existe.
+    fn preedit(&self) -> Option<&str>;
+
+    /// Cursor o selección dentro de `preedit`, en bytes.
+    fn preedit_cursor(&self) -> Option<Range<usize>>;
+
     // -----------------------------------------------------------------------
     // COMBOS INMEDIATOS
     // -----------------------------------------------------------------------
@@ -196,4 +434,70 @@ pub trait ProcessingLayerState {
     ///
     /// Útil para logging, debug o herramientas de editor.
     fn current_state_snapshot(&self) -> Vec<(Self::KeyCode, Self::KeyState)>;
+
+    /// Itera las teclas presionadas actualmente, sin asignar.
+    ///
+    /// Variante de `all_pressed_keys` para consultas por frame.
+    fn pressed_keys_iter(&self) -> impl Iterator<Item = &Self::KeyCode> + '_;
+
+    /// Itera el estado actual de todas las teclas, sin asignar.
+    ///
+    /// Variante de `current_state_snapshot` para consultas por frame.
+    fn key_states_iter(&self) -> impl Iterator<Item = (&Self::KeyCode, Self::KeyState)> + '_;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    const CONFIG: KeyRepeatConfig = KeyRepeatConfig { delay: ms(500), interval: ms(50) };
+
+    #[test]
+    fn no_repeat_before_delay() {
+        assert_eq!(CONFIG.repeats_until(ms(0)), 0);
+        assert_eq!(CONFIG.repeats_until(ms(499)), 0);
+        assert_eq!(CONFIG.repeats_between(ms(100), ms(499)), 0);
+    }
+
+    #[test]
+    fn first_repeat_exactly_at_delay() {
+        assert_eq!(CONFIG.repeats_between(ms(499), ms(500)), 1);
+        // El instante ya contado no se repite en el frame siguiente.
+        assert_eq!(CONFIG.repeats_between(ms(500), ms(549)), 0);
+        assert_eq!(CONFIG.repeats_between(ms(549), ms(550)), 1);
+    }
+
+    #[test]
+    fn long_frame_emits_every_elapsed_interval() {
+        // Repeticiones en 500, 550, 600 y 650.
+        assert_eq!(CONFIG.repeats_between(ms(480), ms(660)), 4);
+        assert_eq!(CONFIG.repeats_between(ms(0), ms(1000)), 11);
+    }
+
+    #[test]
+    fn result_does_not_depend_on_frame_rate() {
+        let coarse = CONFIG.repeats_between(ms(0), ms(2000));
+        let fine: u32 = (0..2000).map(|t| CONFIG.repeats_between(ms(t), ms(t + 1))).sum();
+        assert_eq!(coarse, fine);
+    }
+
+    #[test]
+    fn zero_interval_repeats_once() {
+        let config = KeyRepeatConfig { delay: ms(300), interval: Duration::ZERO };
+        assert_eq!(config.repeats_between(ms(0), ms(299)), 0);
+        assert_eq!(config.repeats_between(ms(0), ms(300)), 1);
+        assert_eq!(config.repeats_between(ms(300), ms(10_000)), 0);
+    }
+
+    #[test]
+    fn zero_delay_repeats_immediately() {
+        let config = KeyRepeatConfig { delay: Duration::ZERO, interval: ms(100) };
+        assert_eq!(config.repeats_until(Duration::ZERO), 1);
+        assert_eq!(config.repeats_between(Duration::ZERO, ms(250)), 2);
+    }
 }
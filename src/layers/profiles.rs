@@ -0,0 +1,236 @@
+//! ---------------------------------------------------------------------------
+//! Contratos del **gestor de perfiles y esquemas de control**.
+//!
+//! Se sitúa por encima de la capa de mapeo y permite:
+//!
+//! - múltiples **perfiles de usuario** locales (jugador 1, jugador 2, invitado...),
+//! - múltiples **esquemas de control** con nombre por perfil
+//!   (`"Classic"`, `"Left-handed"`, `"Southpaw"`...),
+//! - cambio **atómico** entre esquemas,
+//! - duplicado de esquemas completos (análogo a `clone_context`, pero
+//!   abarcando todos los contextos),
+//! - persistencia a través de `export_key_mappings` / `import_key_mappings`.
+//!
+//! Un esquema almacena exactamente lo que exporta `export_key_mappings`:
+//! la capa `User` de todos los contextos. Aplicar un esquema equivale a
+//! importar esos datos en la capa de mapeo.
+//!
+//! Se divide en dos traits:
+//!
+//! - [`ProfileManagerState`]   → Consultas de perfiles y esquemas
+//! - [`ProfileManagerControl`] → Creación, cambio y persistencia
+//!
+//! ---------------------------------------------------------------------------
+
+use alloc::string::String;
+
+use crate::layers::{
+    filters::AccessibilityFilters,
+    mapping_layer::{MappingLayerControl, MappingLayerState},
+};
+
+
+// -----------------------------------------------------------------------------
+// ProfileId
+// -----------------------------------------------------------------------------
+
+/// Identificador de un perfil de usuario local.
+///
+/// Igual que `ContextId`, solo exige poder clonarse y compararse.
+pub trait ProfileId: Clone + Eq {}
+
+
+// -----------------------------------------------------------------------------
+// ProfileManagerState — SOLO LECTURA
+// -----------------------------------------------------------------------------
+
+/// Consultas inmutables sobre perfiles y esquemas de control.
+///
+/// El nombre del esquema activo también es visible desde la capa de mapeo
+/// mediante `MappingLayerState::active_scheme`; aquí se consulta con
+/// `active_profile_scheme`.
+pub trait ProfileManagerState {
+    type Profile: ProfileId;
+    type Mapping: MappingLayerState;
+
+    // -------------------------------------------------------------------------
+    // PROFILES
+    // -------------------------------------------------------------------------
+
+    /// Devuelve el perfil de usuario activo.
+    fn active_profile(&self) -> &Self::Profile;
+
+    /// Devuelve todos los perfiles registrados.
+    fn profiles(&self) -> &[Self::Profile];
+
+    /// Indica si un perfil existe.
+    fn has_profile(&self, profile: &Self::Profile) -> bool;
+
+    // -------------------------------------------------------------------------
+    // SCHEMES
+    // -------------------------------------------------------------------------
+
+    /// Nombre del esquema activo del perfil activo.
+    ///
+    /// Siempre existe: todo perfil tiene un esquema activo, aunque la capa
+    /// de mapeo tenga cambios sin guardar (ver `is_scheme_dirty`).
+    ///
+    /// Puede diferir de `MappingLayerState::active_scheme` de la capa
+    /// gestionada, que retorna `None` cuando los bindings actuales no
+    /// provienen de un esquema guardado (p. ej. si se editaron con
+    /// `mapping_mut` tras un `switch_scheme`).
+    fn active_profile_scheme(&self) -> &str;
+
+    /// Devuelve los nombres de los esquemas del perfil activo.
+    fn schemes(&self) -> &[String];
+
+    /// Devuelve los nombres de los esquemas de un perfil específico.
+    fn schemes_of(&self, profile: &Self::Profile) -> &[String];
+
+    /// Indica si el perfil activo tiene un esquema con ese nombre.
+    fn has_scheme(&self, scheme: &str) -> bool;
+
+    /// Indica si la capa de mapeo tiene cambios no guardados respecto
+    /// al esquema activo.
+    fn is_scheme_dirty(&self) -> bool;
+
+    // -------------------------------------------------------------------------
+    // ACCESSIBILITY
+    // -------------------------------------------------------------------------
+
+    /// Filtros de accesibilidad del perfil activo.
+    fn accessibility_filters(&self) -> &AccessibilityFilters;
+
+    // -------------------------------------------------------------------------
+    // MAPPING
+    // -------------------------------------------------------------------------
+
+    /// Referencia de solo lectura a la capa de mapeo gestionada.
+    fn mapping(&self) -> &Self::Mapping;
+}
+
+
+// -----------------------------------------------------------------------------
+// ProfileManagerControl — CONTROL / MUTACIÓN
+// -----------------------------------------------------------------------------
+
+/// Operaciones de modificación sobre perfiles y esquemas.
+///
+/// Las operaciones que cambian el esquema aplicado validan primero sus
+/// argumentos: si retornan `false`, la capa de mapeo no se ha modificado.
+/// Una vez validadas, el esquema se aplica con `import_key_mappings`, que
+/// no puede fallar pero descarta los overrides de acciones o contextos que
+/// ya no existen en la capa `Default`.
+///
+/// Usado por menús de opciones, el editor y la pantalla de selección de jugador.
+pub trait ProfileManagerControl {
+    type Profile: ProfileId;
+    type Mapping: MappingLayerControl;
+
+    // -------------------------------------------------------------------------
+    // PROFILES
+    // -------------------------------------------------------------------------
+
+    /// Crea un perfil nuevo con un único esquema vacío (solo defaults).
+    ///
+    /// Retorna `false` si el perfil ya existía.
+    fn add_profile(&mut self, profile: Self::Profile) -> bool;
+
+    /// Elimina un perfil y todos sus esquemas.
+    ///
+    /// Retorna `false` si el perfil no existe o es el perfil activo.
+    fn remove_profile(&mut self, profile: &Self::Profile) -> bool;
+
+    /// Cambia el perfil activo y aplica su esquema activo.
+    ///
+    /// Retorna `false`, sin modificar la capa de mapeo, si el perfil no existe.
+    fn set_active_profile(&mut self, profile: &Self::Profile) -> bool;
+
+    // -------------------------------------------------------------------------
+    // SCHEMES
+    // -------------------------------------------------------------------------
+
+    /// Crea un esquema vacío (solo defaults) en el perfil activo.
+    ///
+    /// Retorna `false` si ya existía un esquema con ese nombre.
+    fn add_scheme(&mut self, scheme: &str) -> bool;
+
+    /// Guarda el estado actual de la capa de mapeo en el esquema activo.
+    ///
+    /// Internamente usa `export_key_mappings`.
+    fn save_scheme(&mut self);
+
+    /// Cambia al esquema indicado.
+    ///
+    /// Retorna `false`, sin modificar la capa de mapeo, si el esquema no
+    /// existe en el perfil activo. Si existe, su capa `User` reemplaza la
+    /// actual con `import_key_mappings`; los overrides que apunten a
+    /// acciones o contextos inexistentes se descartan (el resultado puede no
+    /// coincidir exactamente con lo guardado).
+    ///
+    /// Los cambios no guardados del esquema actual se descartan; llamar a
+    /// `save_scheme` antes si deben conservarse.
+    ///
+    /// Actualiza también `MappingLayerControl::set_active_scheme`.
+    fn switch_scheme(&mut self, scheme: &str) -> bool;
+
+    /// Duplica un esquema completo (todos sus contextos) bajo un nombre nuevo.
+    ///
+    /// - `to`: nombre del esquema destino (no debe existir).
+    /// - `from`: esquema origen.
+    ///
+    /// El esquema activo no cambia.
+    fn duplicate_scheme(&mut self, to: &str, from: &str) -> bool;
+
+    /// Renombra un esquema del perfil activo.
+    ///
+    /// Retorna `true` si el esquema antiguo existe y el nuevo nombre está libre.
+    fn rename_scheme(&mut self, old_scheme: &str, new_scheme: &str) -> bool;
+
+    /// Elimina un esquema del perfil activo.
+    ///
+    /// Retorna `false` si no existe o es el esquema activo.
+    fn delete_scheme(&mut self, scheme: &str) -> bool;
+
+    // -------------------------------------------------------------------------
+    // ACCESSIBILITY
+    // -------------------------------------------------------------------------
+
+    /// Establece los filtros de accesibilidad del perfil activo.
+    ///
+    /// Son por perfil, no por esquema: se conservan al cambiar de esquema.
+    /// El runtime debe aplicarlos con `InputFilterExt::set_config` al
+    /// cambiar de perfil.
+    fn set_accessibility_filters(&mut self, filters: AccessibilityFilters);
+
+    // -------------------------------------------------------------------------
+    // MAPPING
+    // -------------------------------------------------------------------------
+
+    /// Acceso mutable a la capa de mapeo gestionada.
+    ///
+    /// Los cambios hechos aquí marcan el esquema activo como modificado
+    /// hasta el próximo `save_scheme`.
+    fn mapping_mut(&mut self) -> &mut Self::Mapping;
+
+    // -------------------------------------------------------------------------
+    // IMPORT / EXPORT (opcional)
+    // -------------------------------------------------------------------------
+
+    /// Exporta todos los perfiles con sus esquemas y filtros de accesibilidad
+    /// en formato serializable.
+    ///
+    /// Cada esquema se serializa con el mismo formato que
+    /// `export_key_mappings`.
+    ///
+    /// Disponible solo con la feature `IE_maping`.
+    #[cfg(feature = "IE_maping")]
+    fn export_profiles<T>(&self) -> T;
+
+    /// Importa perfiles y esquemas, reemplazando los existentes, y aplica el
+    /// esquema activo del perfil activo mediante `import_key_mappings`.
+    ///
+    /// Disponible solo con la feature `IE_maping`.
+    #[cfg(feature = "IE_maping")]
+    fn import_profiles<T>(&mut self, data: T);
+}
@@ -16,7 +16,11 @@
 //!
 //! ---------------------------------------------------------------------------
 
-use std::time::{Duration, Instant};
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+use core::time::Duration;
+
+use super::timestamp::Timestamp;
 
 
 /// ---------------------------------------------------------------------------
@@ -88,26 +92,91 @@ pub trait KeyEventExt : Clone {
     type DeviceKind;
 
     /// Devuelve el código asociado al evento.
+    ///
+    /// Es el identificador **físico** de la tecla (posición en el teclado,
+    /// scancode); equivale a `physical_key`.
     fn keycode(&self) -> Self::KeyCode;
 
+    /// Identificador **físico** de la tecla: su posición en el teclado,
+    /// independiente de la distribución (QWERTY, AZERTY...).
+    ///
+    /// La tecla a la izquierda de `S` es siempre la misma, aunque en AZERTY
+    /// esté rotulada `Q`.
+    fn physical_key(&self) -> Self::KeyCode {
+        self.keycode()
+    }
+
+    /// Identificador **lógico** de la tecla: el símbolo que produce según la
+    /// distribución de teclado activa, expresado con el mismo tipo de código.
+    ///
+    /// Los backends que no conocen la distribución pueden usar la
+    /// implementación por defecto, que devuelve el identificador físico.
+    fn logical_key(&self) -> Self::KeyCode {
+        self.keycode()
+    }
+
     /// Devuelve el estado lógico del evento.
     fn state(&self) -> Self::KeyState;
 
     /// Devuelve el instante en el que ocurrió el evento.
-    fn timestamp(&self) -> Instant;
+    ///
+    /// [`Timestamp`] es portable (`no_std`); con la feature `std` se obtiene
+    /// de un `Instant` con `Timestamp::from_instant`.
+    fn timestamp(&self) -> Timestamp;
 
     /// Devuelve el tipo de dispositivo que generó el evento.
     fn device_kind(&self) -> Self::DeviceKind;
 
+    /// Indica si el evento es una **auto-repetición** del sistema operativo
+    /// (tecla mantenida), y no una pulsación real.
+    ///
+    /// Los backends que no distinguen repeticiones pueden usar la
+    /// implementación por defecto y delegar en el generador por software
+    /// de la capa de procesamiento (ver `KeyRepeatMode`).
+    fn is_repeat(&self) -> bool {
+        false
+    }
+
+    /// Valor analógico del evento, para entradas con eje
+    /// (sticks, gatillos, ruedas...).
+    ///
+    /// Los eventos puramente digitales devuelven `None` (implementación
+    /// por defecto). Para ejes absolutos el rango esperado es `[-1.0, 1.0]`
+    /// o `[0.0, 1.0]`; para ejes relativos (movimiento de mouse, rueda) es
+    /// el delta en unidades del dispositivo.
+    fn axis_value(&self) -> Option<f32> {
+        None
+    }
+
+    /// Tiempo transcurrido desde que ocurrió el evento hasta `now`.
+    ///
+    /// Variante portable de `time_pressed`: `now` debe compartir el origen
+    /// del timestamp del evento.
+    fn time_since(&self, now: Timestamp) -> Duration {
+        now.duration_since(self.timestamp())
+    }
+
     /// Tiempo transcurrido desde que ocurrió el evento.
     ///
     /// Útil para detección de repeticiones rápidas, análisis temporal y
-    /// características avanzadas del sistema de input.
+    /// características avanzadas del sistema de input. Usa el reloj del
+    /// proceso ([`Timestamp::now`]), por lo que requiere la feature `std`.
+    #[cfg(feature = "std")]
     fn time_pressed(&self) -> Duration {
         self.timestamp().elapsed()
     }
 }
 
+/// Eventos cuyo timestamp puede reescribirse.
+///
+/// Lo requieren los filtros que retienen o sintetizan eventos
+/// (`StandardInputFilter`): al entregarlos más tarde los re-estampan con el
+/// instante de entrega para mantener el orden cronológico de la salida.
+pub trait KeyEventRestampExt: KeyEventExt {
+    /// Sustituye el timestamp del evento.
+    fn restamp(&mut self, timestamp: Timestamp);
+}
+
 
 /// ---------------------------------------------------------------------------
 /// Interfaz que marca que un tipo representa un **estado de entrada**.
@@ -125,6 +194,92 @@ pub trait KeyEventExt : Clone {
 pub trait KeyStateExt: Clone {}
 
 
+/// ---------------------------------------------------------------------------
+/// Señales del entorno que **no** son eventos de tecla pero afectan a la
+/// validez del estado de entrada.
+///
+/// Cuando la ventana pierde el foco o la aplicación se suspende, el sistema
+/// operativo deja de enviar eventos de liberación. Sin estas señales, la capa
+/// de procesamiento reportaría teclas "atascadas".
+///
+/// La capa RAW solo las reporta; la reacción (liberar teclas, reconciliar)
+/// pertenece a la capa de procesamiento.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawSignal {
+    /// La ventana perdió el foco de entrada.
+    FocusLost,
+    /// La ventana recuperó el foco de entrada.
+    FocusGained,
+    /// La aplicación fue suspendida (móvil, consola, minimizado...).
+    Suspended,
+    /// La aplicación volvió de una suspensión.
+    Resumed,
+    /// La distribución de teclado activa cambió (ver [`KeyboardLayoutExt`]).
+    LayoutChanged,
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Tabla de traducción de la **distribución de teclado** activa.
+///
+/// Relaciona identificadores físicos (posición) con lógicos (símbolo) para
+/// el teclado actual. La usa la UI para mostrar qué rótulo produce hoy una
+/// tecla física (p. ej. el binding posicional `W` se muestra como `Z` en
+/// AZERTY).
+///
+/// La implementa el backend que conoce la distribución del sistema; debe
+/// actualizarse cuando se emite `RawSignal::LayoutChanged`.
+/// ---------------------------------------------------------------------------
+pub trait KeyboardLayoutExt {
+    type KeyCode: KeyCodeExt;
+
+    /// Identificador de la distribución (p. ej. `"fr-azerty"`, `"en-us"`).
+    fn layout_id(&self) -> &str;
+
+    /// Tecla lógica que produce una tecla física en la distribución activa.
+    ///
+    /// Retorna `None` si la tecla física no produce ningún símbolo.
+    fn logical_for_physical(&self, physical: &Self::KeyCode) -> Option<Self::KeyCode>;
+
+    /// Tecla física que produce un símbolo lógico en la distribución activa.
+    ///
+    /// Retorna `None` si ninguna tecla de la distribución lo produce
+    /// directamente.
+    fn physical_for_logical(&self, logical: &Self::KeyCode) -> Option<Self::KeyCode>;
+}
+
+
+/// ---------------------------------------------------------------------------
+/// Evento del **canal de texto** de la Raw Layer.
+///
+/// El texto Unicode no puede derivarse de `KeyCodeExt`: teclas muertas,
+/// distribuciones de teclado y métodos de entrada (IME) producen caracteres
+/// que no corresponden 1:1 a una tecla. Por eso el texto viaja por un canal
+/// separado de los eventos de tecla.
+///
+/// Los rangos de cursor se expresan en **bytes** sobre el texto UTF-8
+/// de la composición.
+/// ---------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputEvent {
+    /// Texto confirmado, listo para insertarse (incluye resultados de
+    /// teclas muertas y composiciones IME finalizadas).
+    Commit(String),
+    /// Texto en composición (preedit) del IME.
+    ///
+    /// Reemplaza por completo cualquier preedit anterior.
+    Preedit {
+        /// Texto provisional mostrado mientras se compone.
+        text: String,
+        /// Posición del cursor o selección dentro de `text`, si el IME la reporta.
+        cursor: Option<Range<usize>>,
+    },
+    /// La composición terminó o se canceló; el preedit debe ocultarse.
+    PreeditCleared,
+}
+
+
 /// ---------------------------------------------------------------------------
 /// Representa una **capa de entrada cruda** (Raw Input Layer).
 ///
@@ -183,6 +338,14 @@ pub trait KeyStateExt: Clone {}
 /// action_map.resolve(&processed_layer);          // acciones de juego
 /// ```
 ///
+/// En rutas calientes, `poll_events_into` evita la asignación por frame:
+///
+/// ```ignore
+/// raw_events.clear();
+/// raw_layer.poll_events_into(&mut raw_events);   // reutiliza el buffer
+/// processed_layer.update(&raw_events);
+/// ```
+///
 /// La arquitectura se mantiene limpia, modular y multiplataforma.
 /// ---------------------------------------------------------------------------
 pub trait RawInputLayer {
@@ -203,4 +366,36 @@ pub trait RawInputLayer {
     ///
     /// Debe **terminar rápidamente** y **no bloquear**.
     fn poll_events(&mut self) -> Vec<Self::KeyEvent>;
+
+    /// Igual que `poll_events`, pero **añade** los eventos al final de `out`
+    /// en lugar de devolver un vector nuevo.
+    ///
+    /// Pensado para el bucle de frame: reutilizando `out` (vaciado con
+    /// `clear`, que conserva la capacidad) no hay asignaciones una vez que
+    /// el buffer alcanzó su tamaño de trabajo.
+    ///
+    /// La implementación por defecto delega en `poll_events` y asigna un
+    /// vector por llamada: un backend solo cumple lo anterior si la
+    /// sobrescribe.
+    fn poll_events_into(&mut self, out: &mut Vec<Self::KeyEvent>) {
+        out.append(&mut self.poll_events());
+    }
+
+    /// Recolecta las señales de foco y suspensión ocurridas desde la
+    /// última llamada, en orden cronológico.
+    ///
+    /// Los backends sin noción de foco (p. ej. dispositivos headless) pueden
+    /// usar la implementación por defecto, que no reporta ninguna señal.
+    fn poll_signals(&mut self) -> Vec<RawSignal> {
+        Vec::new()
+    }
+
+    /// Recolecta los eventos del canal de texto ocurridos desde la última
+    /// llamada, en orden cronológico.
+    ///
+    /// Los backends sin entrada de texto pueden usar la implementación por
+    /// defecto, que no reporta ningún evento.
+    fn poll_text(&mut self) -> Vec<TextInputEvent> {
+        Vec::new()
+    }
 }
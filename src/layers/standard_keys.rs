@@ -0,0 +1,436 @@
+//! ---------------------------------------------------------------------------
+//! Conjunto **estándar** de teclas y botones.
+//!
+//! Los contratos de la Raw Layer permiten que cada backend defina su propio
+//! tipo de código. Sin embargo, varias herramientas necesitan un vocabulario
+//! común y estable para poder trabajar sin conocer el backend:
+//!
+//! - tablas de nombres legibles y glifos (ver [`crate::layers::key_display`]),
+//! - hojas de controles generadas,
+//! - backends de referencia y scripts de prueba.
+//!
+//! [`StandardKey`] cubre el teclado de 104 teclas, los botones y ejes de
+//! mouse habituales y el layout posicional (botones y ejes) de un gamepad
+//! moderno. Los nombres de
+//! las teclas de teclado son **físicos** (posición en un teclado US), al
+//! estilo de `KeyboardEvent.code` de la web.
+//!
+//! Los backends con su propio tipo de código pueden exponer la equivalencia
+//! implementando [`ToStandardKey`].
+//! ---------------------------------------------------------------------------
+
+use alloc::string::String;
+
+use super::timestamp::Timestamp;
+
+use crate::layers::raw_layer::{KeyCodeExt, KeyEventExt, KeyEventRestampExt, KeyStateExt};
+
+
+/// Familia de dispositivo de un [`StandardKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyGroup {
+    /// Tecla de teclado.
+    Keyboard,
+    /// Botón de mouse.
+    Mouse,
+    /// Botón de gamepad.
+    Gamepad,
+}
+
+
+macro_rules! standard_keys {
+    ($( $group:ident { $( $variant:ident => $id:literal, $name:literal; )* } )*) => {
+        /// Tecla o botón del conjunto estándar.
+        ///
+        /// Los botones de gamepad se nombran por **posición** (`GamepadSouth`
+        /// es A en Xbox, Cruz en PlayStation y B en Nintendo).
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum StandardKey {
+            $( $( $variant, )* )*
+        }
+
+        impl StandardKey {
+            /// Todas las teclas del conjunto, en orden de declaración.
+            pub const ALL: &'static [StandardKey] = &[ $( $( StandardKey::$variant, )* )* ];
+
+            /// Identificador estable en `snake_case` (p. ej. `"shift_left"`).
+            ///
+            /// Apto para ficheros de configuración y scripts.
+            pub fn id(self) -> &'static str {
+                match self {
+                    $( $( StandardKey::$variant => $id, )* )*
+                }
+            }
+
+            /// Nombre legible en inglés (p. ej. `"Left Shift"`).
+            ///
+            /// Es la tabla por defecto de [`crate::layers::key_display::EnglishKeyNames`].
+            pub fn english_name(self) -> &'static str {
+                match self {
+                    $( $( StandardKey::$variant => $name, )* )*
+                }
+            }
+
+            /// Familia de dispositivo a la que pertenece la tecla.
+            pub fn group(self) -> KeyGroup {
+                match self {
+                    $( $( StandardKey::$variant => KeyGroup::$group, )* )*
+                }
+            }
+        }
+    };
+}
+
+standard_keys! {
+    Keyboard {
+        A => "a", "A";
+        B => "b", "B";
+        C => "c", "C";
+        D => "d", "D";
+        E => "e", "E";
+        F => "f", "F";
+        G => "g", "G";
+        H => "h", "H";
+        I => "i", "I";
+        J => "j", "J";
+        K => "k", "K";
+        L => "l", "L";
+        M => "m", "M";
+        N => "n", "N";
+        O => "o", "O";
+        P => "p", "P";
+        Q => "q", "Q";
+        R => "r", "R";
+        S => "s", "S";
+        T => "t", "T";
+        U => "u", "U";
+        V => "v", "V";
+        W => "w", "W";
+        X => "x", "X";
+        Y => "y", "Y";
+        Z => "z", "Z";
+        Digit0 => "digit_0", "0";
+        Digit1 => "digit_1", "1";
+        Digit2 => "digit_2", "2";
+        Digit3 => "digit_3", "3";
+        Digit4 => "digit_4", "4";
+        Digit5 => "digit_5", "5";
+        Digit6 => "digit_6", "6";
+        Digit7 => "digit_7", "7";
+        Digit8 => "digit_8", "8";
+        Digit9 => "digit_9", "9";
+        F1 => "f1", "F1";
+        F2 => "f2", "F2";
+        F3 => "f3", "F3";
+        F4 => "f4", "F4";
+        F5 => "f5", "F5";
+        F6 => "f6", "F6";
+        F7 => "f7", "F7";
+        F8 => "f8", "F8";
+        F9 => "f9", "F9";
+        F10 => "f10", "F10";
+        F11 => "f11", "F11";
+        F12 => "f12", "F12";
+        Escape => "escape", "Escape";
+        Tab => "tab", "Tab";
+        CapsLock => "caps_lock", "Caps Lock";
+        Space => "space", "Space";
+        Enter => "enter", "Enter";
+        Backspace => "backspace", "Backspace";
+        ShiftLeft => "shift_left", "Left Shift";
+        ShiftRight => "shift_right", "Right Shift";
+        ControlLeft => "control_left", "Left Ctrl";
+        ControlRight => "control_right", "Right Ctrl";
+        AltLeft => "alt_left", "Left Alt";
+        AltRight => "alt_right", "Right Alt";
+        SuperLeft => "super_left", "Left Super";
+        SuperRight => "super_right", "Right Super";
+        ContextMenu => "context_menu", "Menu";
+        ArrowUp => "arrow_up", "Up Arrow";
+        ArrowDown => "arrow_down", "Down Arrow";
+        ArrowLeft => "arrow_left", "Left Arrow";
+        ArrowRight => "arrow_right", "Right Arrow";
+        Insert => "insert", "Insert";
+        Delete => "delete", "Delete";
+        Home => "home", "Home";
+        End => "end", "End";
+        PageUp => "page_up", "Page Up";
+        PageDown => "page_down", "Page Down";
+        PrintScreen => "print_screen", "Print Screen";
+        ScrollLock => "scroll_lock", "Scroll Lock";
+        Pause => "pause", "Pause";
+        Backquote => "backquote", "`";
+        Minus => "minus", "-";
+        Equal => "equal", "=";
+        BracketLeft => "bracket_left", "[";
+        BracketRight => "bracket_right", "]";
+        Backslash => "backslash", "\\";
+        Semicolon => "semicolon", ";";
+        Quote => "quote", "'";
+        Comma => "comma", ",";
+        Period => "period", ".";
+        Slash => "slash", "/";
+        NumLock => "num_lock", "Num Lock";
+        Numpad0 => "numpad_0", "Numpad 0";
+        Numpad1 => "numpad_1", "Numpad 1";
+        Numpad2 => "numpad_2", "Numpad 2";
+        Numpad3 => "numpad_3", "Numpad 3";
+        Numpad4 => "numpad_4", "Numpad 4";
+        Numpad5 => "numpad_5", "Numpad 5";
+        Numpad6 => "numpad_6", "Numpad 6";
+        Numpad7 => "numpad_7", "Numpad 7";
+        Numpad8 => "numpad_8", "Numpad 8";
+        Numpad9 => "numpad_9", "Numpad 9";
+        NumpadAdd => "numpad_add", "Numpad +";
+        NumpadSubtract => "numpad_subtract", "Numpad -";
+        NumpadMultiply => "numpad_multiply", "Numpad *";
+        NumpadDivide => "numpad_divide", "Numpad /";
+        NumpadDecimal => "numpad_decimal", "Numpad .";
+        NumpadEnter => "numpad_enter", "Numpad Enter";
+    }
+    Mouse {
+        MouseLeft => "mouse_left", "Left Mouse Button";
+        MouseRight => "mouse_right", "Right Mouse Button";
+        MouseMiddle => "mouse_middle", "Middle Mouse Button";
+        MouseBack => "mouse_back", "Mouse Back";
+        MouseForward => "mouse_forward", "Mouse Forward";
+        MouseMotionX => "mouse_motion_x", "Mouse X";
+        MouseMotionY => "mouse_motion_y", "Mouse Y";
+        MouseWheel => "mouse_wheel", "Mouse Wheel";
+        MouseWheelHorizontal => "mouse_wheel_horizontal", "Mouse Horizontal Wheel";
+    }
+    Gamepad {
+        GamepadSouth => "gamepad_south", "South Button";
+        GamepadEast => "gamepad_east", "East Button";
+        GamepadWest => "gamepad_west", "West Button";
+        GamepadNorth => "gamepad_north", "North Button";
+        GamepadLeftBumper => "gamepad_left_bumper", "Left Bumper";
+        GamepadRightBumper => "gamepad_right_bumper", "Right Bumper";
+        GamepadLeftTrigger => "gamepad_left_trigger", "Left Trigger";
+        GamepadRightTrigger => "gamepad_right_trigger", "Right Trigger";
+        GamepadSelect => "gamepad_select", "Select";
+        GamepadStart => "gamepad_start", "Start";
+        GamepadMode => "gamepad_mode", "Home";
+        GamepadLeftStick => "gamepad_left_stick", "Left Stick Button";
+        GamepadRightStick => "gamepad_right_stick", "Right Stick Button";
+        GamepadDPadUp => "gamepad_dpad_up", "D-Pad Up";
+        GamepadDPadDown => "gamepad_dpad_down", "D-Pad Down";
+        GamepadDPadLeft => "gamepad_dpad_left", "D-Pad Left";
+        GamepadDPadRight => "gamepad_dpad_right", "D-Pad Right";
+        GamepadLeftStickX => "gamepad_left_stick_x", "Left Stick X";
+        GamepadLeftStickY => "gamepad_left_stick_y", "Left Stick Y";
+        GamepadRightStickX => "gamepad_right_stick_x", "Right Stick X";
+        GamepadRightStickY => "gamepad_right_stick_y", "Right Stick Y";
+        GamepadLeftTriggerAxis => "gamepad_left_trigger_axis", "Left Trigger Axis";
+        GamepadRightTriggerAxis => "gamepad_right_trigger_axis", "Right Trigger Axis";
+    }
+}
+
+impl StandardKey {
+    /// Busca una tecla por su identificador estable (ver [`StandardKey::id`]).
+    pub fn from_id(id: &str) -> Option<StandardKey> {
+        StandardKey::ALL.iter().copied().find(|key| key.id() == id)
+    }
+
+    /// Indica si la entrada es un **eje** (valor analógico) y no un botón.
+    ///
+    /// - Ejes relativos: movimiento del mouse y ruedas (valor = delta).
+    /// - Ejes absolutos: sticks en `[-1.0, 1.0]` (Y positivo hacia arriba) y
+    ///   gatillos en `[0.0, 1.0]`.
+    pub fn is_axis(self) -> bool {
+        matches!(
+            self,
+            StandardKey::MouseMotionX
+                | StandardKey::MouseMotionY
+                | StandardKey::MouseWheel
+                | StandardKey::MouseWheelHorizontal
+                | StandardKey::GamepadLeftStickX
+                | StandardKey::GamepadLeftStickY
+                | StandardKey::GamepadRightStickX
+                | StandardKey::GamepadRightStickY
+                | StandardKey::GamepadLeftTriggerAxis
+                | StandardKey::GamepadRightTriggerAxis
+        )
+    }
+
+    /// Busca una tecla por nombre, aceptando tanto el identificador
+    /// (`"shift_left"`) como el nombre de la variante (`"ShiftLeft"`),
+    /// sin distinguir mayúsculas.
+    pub fn from_name(name: &str) -> Option<StandardKey> {
+        let wanted = normalize_name(name);
+        StandardKey::ALL.iter().copied().find(|key| normalize_name(key.id()) == wanted)
+    }
+
+    /// Tecla que produce un carácter ASCII en un teclado US, y si requiere
+    /// `Shift` (`'A'` → `(A, true)`, `'!'` → `(Digit1, true)`).
+    pub fn from_us_char(c: char) -> Option<(StandardKey, bool)> {
+        use StandardKey::*;
+
+        const LETTERS: [StandardKey; 26] = [A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z];
+        const DIGITS: [StandardKey; 10] = [Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9];
+
+        let key = match c {
+            'a'..='z' => (LETTERS[c as usize - 'a' as usize], false),
+            'A'..='Z' => (LETTERS[c as usize - 'A' as usize], true),
+            '0'..='9' => (DIGITS[c as usize - '0' as usize], false),
+            ' ' => (Space, false),
+            '`' => (Backquote, false),
+            '~' => (Backquote, true),
+            '!' => (Digit1, true),
+            '@' => (Digit2, true),
+            '#' => (Digit3, true),
+            '$' => (Digit4, true),
+            '%' => (Digit5, true),
+            '^' => (Digit6, true),
+            '&' => (Digit7, true),
+            '*' => (Digit8, true),
+            '(' => (Digit9, true),
+            ')' => (Digit0, true),
+            '-' => (Minus, false),
+            '_' => (Minus, true),
+            '=' => (Equal, false),
+            '+' => (Equal, true),
+            '[' => (BracketLeft, false),
+            '{' => (BracketLeft, true),
+            ']' => (BracketRight, false),
+            '}' => (BracketRight, true),
+            '\\' => (Backslash, false),
+            '|' => (Backslash, true),
+            ';' => (Semicolon, false),
+            ':' => (Semicolon, true),
+            '\'' => (Quote, false),
+            '"' => (Quote, true),
+            ',' => (Comma, false),
+            '<' => (Comma, true),
+            '.' => (Period, false),
+            '>' => (Period, true),
+            '/' => (Slash, false),
+            '?' => (Slash, true),
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect()
+}
+
+/// `StandardKey` puede usarse directamente como código de tecla en todas
+/// las capas: su representación nativa y de backend es ella misma.
+impl KeyCodeExt for StandardKey {
+    type NativeKey = StandardKey;
+    type BackendKey = StandardKey;
+
+    fn from_native(key: &Self::NativeKey) -> Self {
+        *key
+    }
+
+    fn from_backend(key: &Self::BackendKey) -> Self {
+        *key
+    }
+
+    fn to_native(&self) -> &Self::NativeKey {
+        self
+    }
+
+    fn to_backend(&self) -> Self::BackendKey {
+        *self
+    }
+}
+
+
+/// Equivalencia entre el código de tecla de un backend y el conjunto estándar.
+///
+/// Permite a las herramientas genéricas (nombres, glifos, hojas de controles)
+/// trabajar con cualquier `KeyCodeExt`.
+pub trait ToStandardKey {
+    /// Devuelve la tecla estándar equivalente, si existe.
+    fn to_standard_key(&self) -> Option<StandardKey>;
+}
+
+impl ToStandardKey for StandardKey {
+    fn to_standard_key(&self) -> Option<StandardKey> {
+        Some(*self)
+    }
+}
+
+
+/// Estado de un [`StandardKeyEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardKeyState {
+    /// La tecla o botón se presionó (o se repitió, ver `StandardKeyEvent::repeat`).
+    Pressed,
+    /// La tecla o botón se liberó.
+    Released,
+    /// El valor de un eje cambió (ver `StandardKeyEvent::value`).
+    Changed,
+}
+
+impl KeyStateExt for StandardKeyState {}
+
+/// Dispositivo de origen de un [`StandardKeyEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardDevice {
+    /// Teclado.
+    Keyboard,
+    /// Mouse o touchpad.
+    Mouse,
+    /// Gamepad, identificado por su índice de conexión.
+    Gamepad(u32),
+}
+
+/// Evento crudo sobre el conjunto estándar.
+///
+/// Es el tipo de evento que producen los backends de referencia del crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardKeyEvent {
+    /// Tecla o botón.
+    pub key: StandardKey,
+    /// Transición.
+    pub state: StandardKeyState,
+    /// Instante del evento.
+    pub timestamp: Timestamp,
+    /// Dispositivo de origen.
+    pub device: StandardDevice,
+    /// `true` si es una auto-repetición del sistema.
+    pub repeat: bool,
+    /// Valor analógico, solo para ejes ([`StandardKey::is_axis`]).
+    pub value: Option<f32>,
+}
+
+impl KeyEventExt for StandardKeyEvent {
+    type KeyCode = StandardKey;
+    type KeyState = StandardKeyState;
+    type DeviceKind = StandardDevice;
+
+    fn keycode(&self) -> Self::KeyCode {
+        self.key
+    }
+
+    fn state(&self) -> Self::KeyState {
+        self.state
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    fn device_kind(&self) -> Self::DeviceKind {
+        self.device
+    }
+
+    fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+
+    fn axis_value(&self) -> Option<f32> {
+        self.value
+    }
+}
+
+impl KeyEventRestampExt for StandardKeyEvent {
+    fn restamp(&mut self, timestamp: Timestamp) {
+        self.timestamp = timestamp;
+    }
+}
@@ -0,0 +1,155 @@
+//! ---------------------------------------------------------------------------
+//! Marca de tiempo **portable** de los eventos de entrada.
+//!
+//! `std::time::Instant` no existe en `no_std` (consolas, microcontroladores),
+//! por lo que los contratos usan [`Timestamp`]: un instante monótono
+//! expresado como `Duration` desde un origen arbitrario elegido por el
+//! backend (arranque del sistema, del proceso, primer evento del script...).
+//!
+//! Solo tiene sentido comparar marcas de un mismo origen. Con la feature
+//! `std`, [`Timestamp::now`] y las conversiones desde `Instant` comparten un
+//! origen común por proceso.
+//! ---------------------------------------------------------------------------
+
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::time::Duration;
+
+
+/// Instante monótono portable, como desplazamiento desde un origen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timestamp(Duration);
+
+impl Timestamp {
+    /// El origen.
+    pub const ZERO: Timestamp = Timestamp(Duration::ZERO);
+
+    /// Crea una marca a `offset` del origen.
+    pub const fn from_duration(offset: Duration) -> Self {
+        Self(offset)
+    }
+
+    /// Crea una marca a `micros` microsegundos del origen.
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(Duration::from_micros(micros))
+    }
+
+    /// Crea una marca a `millis` milisegundos del origen.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(Duration::from_millis(millis))
+    }
+
+    /// Desplazamiento desde el origen.
+    pub const fn as_duration(self) -> Duration {
+        self.0
+    }
+
+    /// Tiempo transcurrido desde `earlier`, o cero si `earlier` es posterior.
+    pub fn duration_since(self, earlier: Timestamp) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Tiempo transcurrido desde `earlier`, o `None` si `earlier` es posterior.
+    pub fn checked_duration_since(self, earlier: Timestamp) -> Option<Duration> {
+        self.0.checked_sub(earlier.0)
+    }
+
+    /// Marca `offset` después, o `None` si desborda.
+    pub fn checked_add(self, offset: Duration) -> Option<Timestamp> {
+        self.0.checked_add(offset).map(Timestamp)
+    }
+
+    /// Marca `offset` antes, o `None` si queda antes del origen.
+    pub fn checked_sub(self, offset: Duration) -> Option<Timestamp> {
+        self.0.checked_sub(offset).map(Timestamp)
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, offset: Duration) -> Timestamp {
+        Timestamp(self.0 + offset)
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, offset: Duration) {
+        self.0 += offset;
+    }
+}
+
+/// Resta saturada en el origen.
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, offset: Duration) -> Timestamp {
+        Timestamp(self.0.saturating_sub(offset))
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    fn sub_assign(&mut self, offset: Duration) {
+        *self = *self - offset;
+    }
+}
+
+/// Igual que [`Timestamp::duration_since`] (saturada en cero).
+impl Sub<Timestamp> for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Timestamp) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Reloj del proceso (std)
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+mod process_clock {
+    use std::{sync::OnceLock, time::Instant};
+
+    use super::Timestamp;
+
+    /// Origen común de `Timestamp::now` y de las conversiones desde `Instant`.
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+    fn origin() -> Instant {
+        *ORIGIN.get_or_init(Instant::now)
+    }
+
+    impl Timestamp {
+        /// Instante actual del reloj monótono del proceso.
+        ///
+        /// El origen se fija en la primera llamada a `now`, `from_instant` o
+        /// `to_instant`.
+        pub fn now() -> Timestamp {
+            Timestamp::from_instant(Instant::now())
+        }
+
+        /// Convierte un `Instant` al origen del proceso.
+        ///
+        /// Los instantes anteriores al origen se saturan en [`Timestamp::ZERO`].
+        pub fn from_instant(instant: Instant) -> Timestamp {
+            Timestamp(instant.saturating_duration_since(origin()))
+        }
+
+        /// Convierte a `Instant`, suponiendo el origen del proceso.
+        pub fn to_instant(self) -> Instant {
+            origin() + self.0
+        }
+
+        /// Tiempo transcurrido desde esta marca según el reloj del proceso.
+        pub fn elapsed(self) -> core::time::Duration {
+            Timestamp::now().duration_since(self)
+        }
+    }
+
+    impl From<Instant> for Timestamp {
+        fn from(instant: Instant) -> Timestamp {
+            Timestamp::from_instant(instant)
+        }
+    }
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/igni-engine/igni_input_core/main/assets/vector_color.svg",
     html_favicon_url = "https://raw.githubusercontent.com/igni-engine/igni_input_core/main/assets/vector_color.svg"
@@ -65,15 +66,39 @@
 //!
 //! ## Módulos
 //!
-//! Este crate expone un único módulo público principal:
+//! Este crate expone un módulo público principal:
 //!
 //! - [`layers`](./layers/index.html): contiene todos los traits de las capas Raw, Processing y Mapping.
 //!
+//! Y, de forma opcional, backends de referencia detrás de features:
+//!
+//! - [`backends`](./backends/index.html): implementaciones de `RawInputLayer` (scripts de test, etc.).
+//!
 //! Estas definiciones sirven como contrato para cualquier backend o framework que desee integrarse con IGNI ENGINE.
 //!
 //! ---
+//!
+//! ## `no_std`
+//!
+//! El crate es `no_std` cuando se desactivan las features por defecto:
+//!
+//! | Feature | Contenido                                                         |
+//! |---------|-------------------------------------------------------------------|
+//! | —       | Solo [`Timestamp`](layers::timestamp::Timestamp)                  |
+//! | `alloc` | Todos los contratos de `layers` y `contracts` (usan `Vec` y `String`) |
+//! | `std`   | Lo anterior, más `Timestamp::now`, conversión desde `Instant` y los backends |
+//!
+//! Los tiempos usan `core::time::Duration` y el tipo portable
+//! [`Timestamp`](layers::timestamp::Timestamp) en lugar de `std::time::Instant`.
+//!
+//! ---
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod layers;
+#[cfg(feature = "std")]
+pub mod backends;
 
 
 
@@ -85,5 +110,12 @@ pub mod layers;
 /// disponibles para cualquier crate que implemente un backend o interactue
 /// con el sistema de entrada.
 /// ---------------------------------------------------------------------------
+#[cfg(feature = "alloc")]
 pub mod contracts;
-pub use contracts::{game_contract, runtime_contract};
\ No newline at end of file
+#[cfg(feature = "alloc")]
+pub use contracts::{game_contract, runtime_contract, subscription_contract};
+
+/// Capas concretas mínimas para tests y benches; no forma parte de la API.
+#[doc(hidden)]
+#[cfg(all(feature = "alloc", any(test, feature = "testing")))]
+pub mod testing;
\ No newline at end of file
@@ -0,0 +1,1476 @@
+//! ---------------------------------------------------------------------------
+//! Utilidades compartidas por los tests y benches del crate.
+//!
+//! Oculto y fuera de la API: solo se compila en los tests o con la feature
+//! de desarrollo `testing`. Implementa `ContextId` para `&'static str`.
+//!
+//! Capas concretas mínimas sobre [`StandardKey`] y [`StandardKeyEvent`],
+//! suficientes para montar un [`TestRuntime`] completo sin backend:
+//!
+//! - [`TestMapping`]: contextos con acciones y bindings (solo capa `Default`).
+//! - [`TestProcessing`]: estado por tecla, foco, repeticiones y texto.
+//! - [`TestHistory`]: historial acotado a [`TestHistory::CAPACITY`] eventos.
+//! - [`TestMacros`]: motor que no ejecuta macros ni turbo.
+//! - [`TestSubscriptions`]: registro de suscripciones que anota las entregas.
+//! - [`TestGame`]: `GameContract` sobre las tres capas de lectura.
+//!
+//! Ninguna capa conserva datos que crezcan con cada frame: una vez vistas
+//! todas las teclas de un escenario, el ciclo de frame no asigna.
+//! ---------------------------------------------------------------------------
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use crate::{
+    contracts::{
+        game_contract::GameContract,
+        runtime_contract::{ActionInjection, RuntimeInputExt},
+        subscription_contract::{DeviceChange, InputNotification, InputSubscriptionExt, NotificationKind, SubscriptionHandle},
+    },
+    layers::{
+        action_events::{ActionEvent, ActionPhase},
+        filters::{AccessibilityFilters, InputFilterExt, StandardInputFilter},
+        history::{HistoryControlExt, HistoryStateExt},
+        input_macros::{InputMacro, MacroEngineExt, MacroPolicy},
+        mapping_layer::{
+            BindingLayer, BindingOverride, ContextId, HoldMode, KeyMatchMode, MappingLayerControl, MappingLayerState, ScanConfig,
+        },
+        processing_layer::{
+            InputOrigin, KeyRepeatMode, ProcessingLayerControl, ProcessingLayerState, SyntheticAxisEvent, SyntheticKeyEvent,
+        },
+        raw_layer::{KeyEventExt, RawSignal, TextInputEvent},
+        standard_keys::{StandardDevice, StandardKey, StandardKeyEvent, StandardKeyState},
+        timestamp::Timestamp,
+    },
+};
+
+
+impl ContextId for &'static str {}
+
+/// Notificación de [`TestSubscriptions`].
+pub type TestNotification = InputNotification<&'static str, StandardKey, StandardDevice>;
+
+
+// -----------------------------------------------------------------------------
+// Mapeo
+// -----------------------------------------------------------------------------
+
+/// Última pulsación y última liberación consumidas de una acción
+/// (`GameContract::consume_*`), en microsegundos más uno; `0` si ninguna.
+///
+/// Atómico porque el consumo se registra desde `&self`.
+#[derive(Debug, Default)]
+struct Consumed([AtomicU64; 2]);
+
+impl Clone for Consumed {
+    fn clone(&self) -> Self {
+        Self(core::array::from_fn(|index| AtomicU64::new(self.0[index].load(Ordering::Relaxed))))
+    }
+}
+
+/// Contexto de [`TestMapping`].
+#[derive(Debug, Clone, Default)]
+pub struct TestSection {
+    pub enabled: bool,
+    pub actions: Vec<String>,
+    /// Nombre de cada acción de `actions` para sus eventos, creado una vez.
+    ids: Vec<Arc<str>>,
+    consumed: Vec<Consumed>,
+    pub bindings: Vec<(String, StandardKey)>,
+    pub match_modes: Vec<(String, KeyMatchMode)>,
+    pub hold_modes: Vec<(String, HoldMode)>,
+    pub macros: Vec<InputMacro<StandardKey>>,
+    pub buffers: Vec<(String, Duration)>,
+}
+
+impl TestSection {
+    fn index(&self, action: &str) -> Option<usize> {
+        self.actions.iter().position(|a| a == action)
+    }
+
+    fn has_action(&self, action: &str) -> bool {
+        self.index(action).is_some()
+    }
+
+    fn keys<'a>(&'a self, action: &'a str) -> impl Iterator<Item = &'a StandardKey> + 'a {
+        self.bindings.iter().filter(move |(a, _)| a == action).map(|(_, key)| key)
+    }
+
+    fn add_action(&mut self, action: &str) -> bool {
+        if self.has_action(action) {
+            return false;
+        }
+        self.actions.push(String::from(action));
+        self.ids.push(Arc::from(action));
+        self.consumed.push(Consumed::default());
+        true
+    }
+
+    fn delete_action(&mut self, action: &str) -> bool {
+        let Some(index) = self.index(action) else {
+            return false;
+        };
+        self.actions.remove(index);
+        self.ids.remove(index);
+        self.consumed.remove(index);
+        self.bindings.retain(|(a, _)| a != action);
+        self.match_modes.retain(|(a, _)| a != action);
+        self.hold_modes.retain(|(a, _)| a != action);
+        self.macros.retain(|m| m.trigger != action);
+        self.buffers.retain(|(a, _)| a != action);
+        true
+    }
+
+    fn rename_action(&mut self, old_action: &str, new_action: &str) -> bool {
+        let Some(index) = self.index(old_action) else {
+            return false;
+        };
+        if self.has_action(new_action) {
+            return false;
+        }
+        self.actions[index] = String::from(new_action);
+        self.ids[index] = Arc::from(new_action);
+        let renamed = self
+            .bindings
+            .iter_mut()
+            .map(|(a, _)| a)
+            .chain(self.match_modes.iter_mut().map(|(a, _)| a))
+            .chain(self.hold_modes.iter_mut().map(|(a, _)| a))
+            .chain(self.macros.iter_mut().map(|m| &mut m.trigger))
+            .chain(self.buffers.iter_mut().map(|(a, _)| a));
+        for action in renamed.filter(|a| *a == old_action) {
+            *action = String::from(new_action);
+        }
+        true
+    }
+
+    fn map(&mut self, action: &str, key: StandardKey) -> bool {
+        if !self.has_action(action) {
+            return false;
+        }
+        self.bindings.retain(|(a, _)| a != action);
+        self.bindings.push((String::from(action), key));
+        true
+    }
+
+    fn unmap(&mut self, action: &str) -> bool {
+        self.bindings.retain(|(a, _)| a != action);
+        self.has_action(action)
+    }
+
+    /// Sustituye (o elimina con `None`) la entrada de `action` en `list`.
+    fn set<T>(list: &mut Vec<(String, T)>, action: &str, value: Option<T>) {
+        list.retain(|(a, _)| a != action);
+        if let Some(value) = value {
+            list.push((String::from(action), value));
+        }
+    }
+
+    fn get<'a, T>(list: &'a [(String, T)], action: &str) -> Option<&'a T> {
+        list.iter().find(|(a, _)| a == action).map(|(_, value)| value)
+    }
+}
+
+/// Inyección directa de una acción (`MappingLayerControl::inject_action`).
+#[derive(Debug, Clone)]
+struct Injection {
+    id: Arc<str>,
+    frames: u32,
+    started: bool,
+}
+
+/// Estado de mapeo mínimo: contextos con sus acciones y bindings.
+///
+/// - No hay overrides de usuario: todo binding es de la capa `Default`, y
+///   los `reset_*` no tienen nada que restaurar.
+/// - Debe tener al menos un contexto; el primero es el activo al crearlo.
+/// - `resolve_actions` emite `Started` y `Performed` al pulsarse una tecla
+///   de la acción y `Canceled` al soltarse la última, con el instante del
+///   frame (`HistoryStateExt::current_time`) y el dispositivo del último
+///   evento del frame. No hay buffering, escaneo ni modo toggle: solo se
+///   guardan sus configuraciones.
+#[derive(Debug, Clone, Default)]
+pub struct TestMapping {
+    contexts: Vec<&'static str>,
+    sections: Vec<TestSection>,
+    current: usize,
+    scan: Option<ScanConfig<StandardKey>>,
+    text_focus: bool,
+    text_allowed: Vec<String>,
+    turbo: Vec<(StandardKey, Duration)>,
+    active_scheme: Option<String>,
+    events: Vec<ActionEvent<StandardDevice>>,
+    injected: Vec<Injection>,
+}
+
+impl TestMapping {
+    /// Añade un contexto habilitado con las acciones y teclas indicadas, en orden.
+    pub fn with_context(mut self, ctx: &'static str, bindings: &[(&str, &[StandardKey])]) -> Self {
+        let mut section = TestSection { enabled: true, ..Default::default() };
+        for (action, keys) in bindings {
+            section.add_action(action);
+            section.bindings.extend(keys.iter().map(|key| (String::from(*action), *key)));
+        }
+        self.contexts.push(ctx);
+        self.sections.push(section);
+        self
+    }
+
+    /// Deshabilita un contexto existente.
+    pub fn disabled(mut self, ctx: &'static str) -> Self {
+        self.disable_context(&ctx);
+        self
+    }
+
+    /// Indica si la acción está activa por `inject_action`.
+    pub fn is_injected(&self, action: &str) -> bool {
+        self.injected.iter().any(|injection| injection.started && *injection.id == *action)
+    }
+
+    fn section(&self, ctx: &&'static str) -> Option<&TestSection> {
+        self.contexts.iter().position(|c| c == ctx).map(|index| &self.sections[index])
+    }
+
+    fn section_mut(&mut self, ctx: &&'static str) -> Option<&mut TestSection> {
+        self.contexts.iter().position(|c| c == ctx).map(|index| &mut self.sections[index])
+    }
+
+    fn current_section(&self) -> &TestSection {
+        &self.sections[self.current]
+    }
+
+    fn current_section_mut(&mut self) -> &mut TestSection {
+        &mut self.sections[self.current]
+    }
+
+    /// Aplica `op` a todos los contextos; `true` si alguno la aceptó.
+    fn all(&mut self, mut op: impl FnMut(&mut TestSection) -> bool) -> bool {
+        self.sections.iter_mut().fold(false, |any, section| op(section) | any)
+    }
+
+    fn consumed(&self, action: &str, slot: usize) -> Option<&AtomicU64> {
+        let section = self.current_section();
+        section.index(action).map(|index| &section.consumed[index].0[slot])
+    }
+
+    /// Indica si la transición de `action` en `at` ya se consumió.
+    ///
+    /// `slot` es `0` para pulsaciones y `1` para liberaciones.
+    pub fn is_consumed(&self, action: &str, slot: usize, at: Duration) -> bool {
+        self.consumed(action, slot).is_some_and(|mark| mark.load(Ordering::Relaxed) == consumed_mark(at))
+    }
+
+    /// Consume la transición de `action` en `at`; `false` si ya lo estaba.
+    pub fn consume(&self, action: &str, slot: usize, at: Duration) -> bool {
+        self.consumed(action, slot).is_some_and(|mark| mark.swap(consumed_mark(at), Ordering::Relaxed) != consumed_mark(at))
+    }
+}
+
+fn consumed_mark(at: Duration) -> u64 {
+    u64::try_from(at.as_micros()).unwrap_or(u64::MAX - 1) + 1
+}
+
+fn action_event(
+    action: &Arc<str>,
+    phase: ActionPhase,
+    device: Option<StandardDevice>,
+    origin: InputOrigin,
+    timestamp: Timestamp,
+    value: f32,
+) -> ActionEvent<StandardDevice> {
+    ActionEvent { action: Arc::clone(action), phase, device, origin, timestamp, value }
+}
+
+impl MappingLayerState for TestMapping {
+    type KeyCode = StandardKey;
+    type Ctx = &'static str;
+    type DeviceKind = StandardDevice;
+
+    fn current_context(&self) -> &Self::Ctx { &self.contexts[self.current] }
+    fn contexts(&self) -> &[Self::Ctx] { &self.contexts }
+    fn has_context(&self, ctx: &Self::Ctx) -> bool { self.section(ctx).is_some() }
+    fn key_for_action(&self, action: &str) -> Option<Self::KeyCode> { self.key_for_action_in(self.current_context(), action) }
+    fn key_for_action_in(&self, ctx: &Self::Ctx, action: &str) -> Option<Self::KeyCode> {
+        self.bindings_in(ctx).iter().find(|(a, _)| a == action).map(|(_, key)| *key)
+    }
+    fn has_action(&self, action: &str) -> bool { self.has_action_in(self.current_context(), action) }
+    fn has_action_in(&self, ctx: &Self::Ctx, action: &str) -> bool { self.actions_in(ctx).iter().any(|a| a == action) }
+    fn is_action_mapped(&self, action: &str) -> bool { self.key_for_action(action).is_some() }
+    fn is_action_mapped_in(&self, ctx: &Self::Ctx, action: &str) -> bool { self.key_for_action_in(ctx, action).is_some() }
+    fn actions(&self) -> &[String] { self.actions_in(self.current_context()) }
+    fn actions_in(&self, ctx: &Self::Ctx) -> &[String] { self.section(ctx).map_or(&[], |section| &section.actions) }
+    fn actions_for_key(&self, _key: &Self::KeyCode) -> &[String] { &[] }
+    fn actions_for_key_in(&self, _ctx: &Self::Ctx, _key: &Self::KeyCode) -> &[String] { &[] }
+    fn is_key_mapped(&self, key: &Self::KeyCode) -> bool { self.is_key_mapped_in(self.current_context(), key) }
+    fn is_key_mapped_in(&self, ctx: &Self::Ctx, key: &Self::KeyCode) -> bool { self.bindings_in(ctx).iter().any(|(_, k)| k == key) }
+    fn bindings(&self) -> &[(String, Self::KeyCode)] { self.bindings_in(self.current_context()) }
+    fn bindings_in(&self, ctx: &Self::Ctx) -> &[(String, Self::KeyCode)] { self.section(ctx).map_or(&[], |section| &section.bindings) }
+    fn is_context_enabled(&self, ctx : &Self::Ctx) -> bool { self.section(ctx).is_some_and(|section| section.enabled) }
+    fn default_key_for_action_in(&self, ctx: &Self::Ctx, action: &str) -> Option<Self::KeyCode> { self.key_for_action_in(ctx, action) }
+    fn user_override_in(&self, _ctx: &Self::Ctx, _action: &str) -> Option<&BindingOverride<Self::KeyCode>> { None }
+    fn binding_layer_in(&self, ctx: &Self::Ctx, action: &str) -> Option<BindingLayer> {
+        self.is_action_mapped_in(ctx, action).then_some(BindingLayer::Default)
+    }
+    fn default_bindings_in(&self, ctx: &Self::Ctx) -> &[(String, Self::KeyCode)] { self.bindings_in(ctx) }
+    fn user_overrides_in(&self, _ctx: &Self::Ctx) -> &[(String, BindingOverride<Self::KeyCode>)] { &[] }
+    fn has_user_overrides(&self) -> bool { false }
+    fn key_match_mode(&self, action: &str) -> KeyMatchMode { self.key_match_mode_in(self.current_context(), action) }
+    fn key_match_mode_in(&self, ctx: &Self::Ctx, action: &str) -> KeyMatchMode {
+        self.section(ctx).and_then(|section| TestSection::get(&section.match_modes, action)).copied().unwrap_or_default()
+    }
+    fn hold_mode(&self, action: &str) -> HoldMode { self.hold_mode_in(self.current_context(), action) }
+    fn hold_mode_in(&self, ctx: &Self::Ctx, action: &str) -> HoldMode {
+        self.section(ctx).and_then(|section| TestSection::get(&section.hold_modes, action)).copied().unwrap_or_default()
+    }
+    fn is_toggled_on(&self, _action: &str) -> bool { false }
+    fn scan_config(&self) -> Option<&ScanConfig<Self::KeyCode>> { self.scan.as_ref() }
+    fn scan_highlighted_action(&self) -> Option<&str> { self.scan.as_ref().and(self.actions().first().map(String::as_str)) }
+    fn is_scan_paused(&self) -> bool { false }
+    fn has_text_focus(&self) -> bool { self.text_focus }
+    fn is_action_allowed_in_text_focus(&self, action: &str) -> bool { self.text_allowed.iter().any(|a| a == action) }
+    fn macros(&self) -> &[InputMacro<Self::KeyCode>] { self.macros_in(self.current_context()) }
+    fn macros_in(&self, ctx: &Self::Ctx) -> &[InputMacro<Self::KeyCode>] { self.section(ctx).map_or(&[], |section| &section.macros) }
+    fn turbo_interval(&self, key: &Self::KeyCode) -> Option<Duration> { self.turbo.iter().find(|(k, _)| k == key).map(|(_, interval)| *interval) }
+    fn buffer_window(&self, action: &str) -> Option<Duration> { self.buffer_window_in(self.current_context(), action) }
+    fn buffer_window_in(&self, ctx: &Self::Ctx, action: &str) -> Option<Duration> {
+        self.section(ctx).and_then(|section| TestSection::get(&section.buffers, action)).copied()
+    }
+    fn active_scheme(&self) -> Option<&str> { self.active_scheme.as_deref() }
+    fn action_events(&self) -> &[ActionEvent<Self::DeviceKind>] { &self.events }
+    // El formato de exportación lo elige quien llama, sin restricciones:
+    // ningún tipo concreto puede construirlo, y sin overrides no hay nada
+    // que exportar.
+    #[cfg(feature = "IE_maping")]
+    fn export_key_mappings<T>(&self) -> T { panic!("TestMapping no tiene capa User que exportar") }
+}
+
+impl MappingLayerControl for TestMapping {
+    type KeyCode = StandardKey;
+    type Ctx = &'static str;
+    type DeviceKind = StandardDevice;
+
+    fn set_current_context(&mut self, ctx: Self::Ctx) -> bool {
+        let Some(index) = self.contexts.iter().position(|c| *c == ctx) else {
+            return false;
+        };
+        self.current = index;
+        true
+    }
+    fn map_action(&mut self, action: &str, key: Self::KeyCode) -> bool { self.current_section_mut().map(action, key) }
+    fn unmap_action(&mut self, action: &str) -> bool { self.current_section_mut().unmap(action) }
+    fn map_action_in(&mut self, ctx: &Self::Ctx, action: &str, key: Self::KeyCode) -> bool {
+        self.section_mut(ctx).is_some_and(|section| section.map(action, key))
+    }
+    fn unmap_action_in(&mut self, ctx: &Self::Ctx, action: &str) -> bool { self.section_mut(ctx).is_some_and(|section| section.unmap(action)) }
+    fn map_action_all(&mut self, action: &str, key: Self::KeyCode) -> bool { self.all(|section| section.map(action, key)) }
+    fn unmap_action_all(&mut self, action: &str) -> bool { self.all(|section| section.unmap(action)) }
+    fn remove_context(&mut self, ctx: &Self::Ctx) -> bool {
+        let Some(index) = self.contexts.iter().position(|c| c == ctx) else {
+            return false;
+        };
+        self.contexts.remove(index);
+        self.sections.remove(index);
+        if self.current >= index {
+            self.current = self.current.saturating_sub(1);
+        }
+        true
+    }
+    fn add_context(&mut self, ctx: Self::Ctx) -> bool {
+        if self.has_context(&ctx) {
+            return false;
+        }
+        self.contexts.push(ctx);
+        self.sections.push(TestSection { enabled: true, ..Default::default() });
+        true
+    }
+    fn rename_action(&mut self, old_action: &str, new_action: &str) -> bool { self.current_section_mut().rename_action(old_action, new_action) }
+    fn rename_action_in(&mut self, ctx: &Self::Ctx, old_action: &str, new_action: &str) -> bool {
+        self.section_mut(ctx).is_some_and(|section| section.rename_action(old_action, new_action))
+    }
+    fn rename_action_all(&mut self, old_action: &str, new_action: &str) -> bool { self.all(|section| section.rename_action(old_action, new_action)) }
+    fn add_action(&mut self, action: &str) -> bool { self.current_section_mut().add_action(action) }
+    fn add_action_in(&mut self, ctx: &Self::Ctx, action: &str) -> bool { self.section_mut(ctx).is_some_and(|section| section.add_action(action)) }
+    fn add_action_all(&mut self, action: &str) -> bool { self.all(|section| section.add_action(action)) }
+    fn delete_action(&mut self, action: &str) -> bool { self.current_section_mut().delete_action(action) }
+    fn delete_action_in(&mut self, ctx: &Self::Ctx, action: &str) -> bool { self.section_mut(ctx).is_some_and(|section| section.delete_action(action)) }
+    fn delete_action_all(&mut self, action: &str) -> bool { self.all(|section| section.delete_action(action)) }
+    fn delete_all_actions(&mut self) -> bool {
+        let ctx = *self.current_context();
+        self.delete_all_actions_in(&ctx)
+    }
+    fn delete_all_actions_in(&mut self, ctx: &Self::Ctx) -> bool {
+        self.section_mut(ctx).is_some_and(|section| {
+            let had_actions = !section.actions.is_empty();
+            *section = TestSection { enabled: section.enabled, ..Default::default() };
+            had_actions
+        })
+    }
+    fn clone_context(&mut self, to: &Self::Ctx, from: Self::Ctx) -> bool {
+        let Some(source) = self.section(&from).cloned() else {
+            return false;
+        };
+        match self.section_mut(to) {
+            Some(section) => *section = source,
+            None => {
+                self.contexts.push(*to);
+                self.sections.push(source);
+            }
+        }
+        true
+    }
+    fn reset_context(&mut self) -> bool { false }
+    fn reset_context_in(&mut self, _ctx: &Self::Ctx) -> bool { false }
+    fn reset_all_contexts(&mut self) {}
+    fn reset_action_in(&mut self, _ctx: &Self::Ctx, _action: &str) -> bool { false }
+    fn enable_context(&mut self, ctx: &Self::Ctx) -> bool {
+        self.section_mut(ctx).map(|section| section.enabled = true).is_some()
+    }
+    fn disable_context(&mut self, ctx: &Self::Ctx) -> bool {
+        self.section_mut(ctx).map(|section| section.enabled = false).is_some()
+    }
+    fn set_key_match_mode(&mut self, action: &str, mode: KeyMatchMode) -> bool {
+        let ctx = *self.current_context();
+        self.set_key_match_mode_in(&ctx, action, mode)
+    }
+    fn set_key_match_mode_in(&mut self, ctx: &Self::Ctx, action: &str, mode: KeyMatchMode) -> bool {
+        self.section_mut(ctx).filter(|section| section.has_action(action)).map(|section| TestSection::set(&mut section.match_modes, action, Some(mode))).is_some()
+    }
+    fn set_hold_mode(&mut self, action: &str, mode: HoldMode) -> bool {
+        let ctx = *self.current_context();
+        self.set_hold_mode_in(&ctx, action, mode)
+    }
+    fn set_hold_mode_in(&mut self, ctx: &Self::Ctx, action: &str, mode: HoldMode) -> bool {
+        self.section_mut(ctx).filter(|section| section.has_action(action)).map(|section| TestSection::set(&mut section.hold_modes, action, Some(mode))).is_some()
+    }
+    fn set_scan_mode(&mut self, config: Option<ScanConfig<Self::KeyCode>>) { self.scan = config; }
+    fn set_text_focus(&mut self, focused: bool) { self.text_focus = focused; }
+    fn allow_action_in_text_focus(&mut self, action: &str, allow: bool) -> bool {
+        if !self.has_action(action) {
+            return false;
+        }
+        self.text_allowed.retain(|a| a != action);
+        if allow {
+            self.text_allowed.push(String::from(action));
+        }
+        true
+    }
+    fn set_macro(&mut self, input_macro: InputMacro<Self::KeyCode>) -> bool {
+        let ctx = *self.current_context();
+        self.set_macro_in(&ctx, input_macro)
+    }
+    fn set_macro_in(&mut self, ctx: &Self::Ctx, input_macro: InputMacro<Self::KeyCode>) -> bool {
+        let Some(section) = self.section_mut(ctx).filter(|section| section.has_action(&input_macro.trigger)) else {
+            return false;
+        };
+        section.macros.retain(|m| m.name != input_macro.name);
+        section.macros.push(input_macro);
+        true
+    }
+    fn remove_macro(&mut self, name: &str) -> bool {
+        let ctx = *self.current_context();
+        self.remove_macro_in(&ctx, name)
+    }
+    fn remove_macro_in(&mut self, ctx: &Self::Ctx, name: &str) -> bool {
+        self.section_mut(ctx).is_some_and(|section| {
+            let before = section.macros.len();
+            section.macros.retain(|m| m.name != name);
+            section.macros.len() != before
+        })
+    }
+    fn set_turbo(&mut self, key: Self::KeyCode, interval: Option<Duration>) {
+        self.turbo.retain(|(k, _)| *k != key);
+        if let Some(interval) = interval {
+            self.turbo.push((key, interval));
+        }
+    }
+    fn set_buffer_window(&mut self, action: &str, window: Option<Duration>) -> bool {
+        let ctx = *self.current_context();
+        self.set_buffer_window_in(&ctx, action, window)
+    }
+    fn set_buffer_window_in(&mut self, ctx: &Self::Ctx, action: &str, window: Option<Duration>) -> bool {
+        self.section_mut(ctx).filter(|section| section.has_action(action)).map(|section| TestSection::set(&mut section.buffers, action, window)).is_some()
+    }
+    fn set_active_scheme(&mut self, scheme: Option<&str>) { self.active_scheme = scheme.map(String::from); }
+    // Sin capa User, los datos importados no tienen dónde aplicarse.
+    #[cfg(feature = "IE_maping")]
+    fn import_key_mappings<T>(&mut self, _data: T) {}
+    #[cfg(feature = "IE_maping")]
+    fn import_default_mappings<T>(&mut self, _data: T) {}
+    fn begin_frame(&mut self) { self.events.clear(); }
+    fn resolve_actions(&mut self, processing: &impl ProcessingLayerState<KeyCode = Self::KeyCode, Event: KeyEventExt<DeviceKind = Self::DeviceKind>>, history: &impl HistoryStateExt<KeyCode = Self::KeyCode>) {
+        let timestamp = Timestamp::from_duration(history.current_time());
+        let last_device = processing.frame_events().last().map(|event| event.device_kind());
+        let section = &self.sections[self.current];
+        if section.enabled {
+            for (action, id) in section.actions.iter().zip(&section.ids) {
+                let pressed = section.keys(action).any(|key| processing.just_pressed(key));
+                let released = !pressed
+                    && section.keys(action).any(|key| processing.just_released(key))
+                    && !section.keys(action).any(|key| processing.is_pressed(key));
+                if !pressed && !released {
+                    continue;
+                }
+                let origin = section.keys(action).find_map(|key| processing.key_origin(key)).unwrap_or_default();
+                let device = if origin == InputOrigin::Synthetic { None } else { last_device };
+                if pressed {
+                    let value = section.keys(action).map(|key| processing.axis_value(key)).fold(0.0, f32::max);
+                    self.events.push(action_event(id, ActionPhase::Started, device, origin, timestamp, value));
+                    self.events.push(action_event(id, ActionPhase::Performed, device, origin, timestamp, value));
+                } else {
+                    self.events.push(action_event(id, ActionPhase::Canceled, device, origin, timestamp, 0.0));
+                }
+            }
+        }
+
+        let events = &mut self.events;
+        self.injected.retain_mut(|injection| {
+            let origin = InputOrigin::Synthetic;
+            if !injection.started {
+                injection.started = true;
+                events.push(action_event(&injection.id, ActionPhase::Started, None, origin, timestamp, 1.0));
+                events.push(action_event(&injection.id, ActionPhase::Performed, None, origin, timestamp, 1.0));
+                return true;
+            }
+            if injection.frames > 0 {
+                return true;
+            }
+            events.push(action_event(&injection.id, ActionPhase::Canceled, None, origin, timestamp, 0.0));
+            false
+        });
+    }
+    fn inject_action(&mut self, action: &str, frames: u32) -> bool {
+        let section = self.current_section();
+        let Some(index) = section.index(action).filter(|_| frames > 0) else {
+            return false;
+        };
+        let id = Arc::clone(&section.ids[index]);
+        self.injected.push(Injection { id, frames, started: false });
+        true
+    }
+    fn drain_action_events(&mut self) -> Vec<ActionEvent<Self::DeviceKind>> { core::mem::take(&mut self.events) }
+    fn drain_action_events_into(&mut self, out: &mut Vec<ActionEvent<Self::DeviceKind>>) { out.append(&mut self.events); }
+    fn end_frame(&mut self) {
+        for injection in self.injected.iter_mut().filter(|injection| injection.started) {
+            injection.frames = injection.frames.saturating_sub(1);
+        }
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Procesamiento
+// -----------------------------------------------------------------------------
+
+/// Estado de una tecla vista alguna vez.
+#[derive(Debug, Clone)]
+struct KeyEntry {
+    key: StandardKey,
+    state: StandardKeyState,
+    value: f32,
+    origin: InputOrigin,
+    pressed_at: Option<Timestamp>,
+    released_at: Option<Timestamp>,
+    just_pressed: bool,
+    just_released: bool,
+    repeats: u32,
+}
+
+/// Indica si el eje acumula deltas dentro del frame (mouse y ruedas).
+fn is_relative(key: StandardKey) -> bool {
+    matches!(
+        key,
+        StandardKey::MouseMotionX | StandardKey::MouseMotionY | StandardKey::MouseWheel | StandardKey::MouseWheelHorizontal
+    )
+}
+
+/// Capa de procesamiento mínima.
+///
+/// Las entradas nunca se eliminan: una vez vistas todas las teclas del
+/// escenario, actualizar y consultar no asigna. Mientras se reconcilia el
+/// foco (con `set_suppress_held_on_focus(true)`, el valor por defecto),
+/// ninguna pulsación genera `just_pressed`.
+#[derive(Debug, Clone)]
+pub struct TestProcessing {
+    keys: Vec<KeyEntry>,
+    frame_events: Vec<StandardKeyEvent>,
+    frame_text: String,
+    preedit: Option<(String, Option<Range<usize>>)>,
+    now: Timestamp,
+    /// `now` al empezar el frame (repeticiones por software).
+    frame_start: Timestamp,
+    focused: bool,
+    reconciling: bool,
+    /// El foco volvió durante este frame.
+    regained: bool,
+    suppress_held_on_focus: bool,
+    repeat_mode: KeyRepeatMode,
+}
+
+impl Default for TestProcessing {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            frame_events: Vec::new(),
+            frame_text: String::new(),
+            preedit: None,
+            now: Timestamp::ZERO,
+            frame_start: Timestamp::ZERO,
+            focused: true,
+            reconciling: false,
+            regained: false,
+            suppress_held_on_focus: true,
+            repeat_mode: KeyRepeatMode::Backend,
+        }
+    }
+}
+
+impl TestProcessing {
+    fn entry(&self, key: &StandardKey) -> Option<&KeyEntry> {
+        self.keys.iter().find(|entry| entry.key == *key)
+    }
+
+    fn apply(&mut self, key: StandardKey, state: StandardKeyState, value: Option<f32>, repeat: bool, at: Timestamp, origin: InputOrigin) {
+        self.now = self.now.max(at);
+        let index = match self.keys.iter().position(|entry| entry.key == key) {
+            Some(index) => index,
+            None => {
+                self.keys.push(KeyEntry {
+                    key,
+                    state: StandardKeyState::Released,
+                    value: 0.0,
+                    origin,
+                    pressed_at: None,
+                    released_at: None,
+                    just_pressed: false,
+                    just_released: false,
+                    repeats: 0,
+                });
+                self.keys.len() - 1
+            }
+        };
+        let suppress = self.reconciling && self.suppress_held_on_focus;
+        let backend_repeats = self.repeat_mode == KeyRepeatMode::Backend;
+        let entry = &mut self.keys[index];
+        entry.origin = origin;
+        match state {
+            StandardKeyState::Pressed if repeat => {
+                if backend_repeats {
+                    entry.repeats += 1;
+                }
+            }
+            StandardKeyState::Pressed => {
+                if entry.state != StandardKeyState::Pressed {
+                    entry.just_pressed = !suppress;
+                    entry.pressed_at = Some(at);
+                }
+                entry.value = 1.0;
+            }
+            StandardKeyState::Released => {
+                if entry.state == StandardKeyState::Pressed {
+                    entry.just_released = true;
+                    entry.released_at = Some(at);
+                }
+                entry.value = 0.0;
+            }
+            StandardKeyState::Changed if is_relative(key) => entry.value += value.unwrap_or(0.0),
+            StandardKeyState::Changed => entry.value = value.unwrap_or(0.0),
+        }
+        entry.state = state;
+    }
+}
+
+impl ProcessingLayerState for TestProcessing {
+    type KeyCode = StandardKey;
+    type KeyState = StandardKeyState;
+    type Event = StandardKeyEvent;
+
+    fn is_pressed(&self, key: &StandardKey) -> bool {
+        self.entry(key).is_some_and(|entry| entry.state == StandardKeyState::Pressed)
+    }
+
+    fn is_released(&self, key: &StandardKey) -> bool {
+        !self.is_pressed(key)
+    }
+
+    fn is_held(&self, key: &StandardKey) -> bool {
+        self.is_pressed(key)
+    }
+
+    fn get_key_state(&self, key: &StandardKey) -> StandardKeyState {
+        self.entry(key).map_or(StandardKeyState::Released, |entry| entry.state)
+    }
+
+    fn axis_value(&self, key: &StandardKey) -> f32 {
+        self.entry(key).map_or(0.0, |entry| entry.value)
+    }
+
+    fn key_origin(&self, key: &StandardKey) -> Option<InputOrigin> {
+        self.entry(key).map(|entry| entry.origin)
+    }
+
+    fn is_logical_pressed(&self, key: &StandardKey) -> bool {
+        self.is_pressed(key)
+    }
+
+    fn logical_just_pressed(&self, key: &StandardKey) -> bool {
+        self.just_pressed(key)
+    }
+
+    fn logical_just_released(&self, key: &StandardKey) -> bool {
+        self.just_released(key)
+    }
+
+    fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    fn is_reconciling(&self) -> bool {
+        self.reconciling
+    }
+
+    fn just_pressed(&self, key: &StandardKey) -> bool {
+        self.entry(key).is_some_and(|entry| entry.just_pressed)
+    }
+
+    fn just_released(&self, key: &StandardKey) -> bool {
+        self.entry(key).is_some_and(|entry| entry.just_released)
+    }
+
+    fn just_repeated(&self, key: &StandardKey) -> bool {
+        self.repeat_count(key) > 0
+    }
+
+    fn repeat_count(&self, key: &StandardKey) -> u32 {
+        self.entry(key).map_or(0, |entry| entry.repeats)
+    }
+
+    fn any_key_just_pressed(&self) -> bool {
+        self.keys.iter().any(|entry| entry.just_pressed)
+    }
+
+    fn any_key_just_released(&self) -> bool {
+        self.keys.iter().any(|entry| entry.just_released)
+    }
+
+    fn frame_events(&self) -> &[StandardKeyEvent] {
+        &self.frame_events
+    }
+
+    fn frame_text(&self) -> &str {
+        &self.frame_text
+    }
+
+    fn preedit(&self) -> Option<&str> {
+        self.preedit.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    fn preedit_cursor(&self) -> Option<Range<usize>> {
+        self.preedit.as_ref().and_then(|(_, cursor)| cursor.clone())
+    }
+
+    fn combo_pressed(&self, keys: &[StandardKey]) -> bool {
+        keys.iter().all(|key| self.is_pressed(key))
+    }
+
+    fn just_pressed_combo(&self, keys: &[StandardKey]) -> bool {
+        self.combo_pressed(keys) && keys.iter().any(|key| self.just_pressed(key))
+    }
+
+    fn pressed_duration(&self, key: &StandardKey) -> Option<Duration> {
+        let entry = self.entry(key).filter(|entry| entry.state == StandardKeyState::Pressed)?;
+        Some(self.now.duration_since(entry.pressed_at?))
+    }
+
+    fn time_since_release(&self, key: &StandardKey) -> Option<Duration> {
+        let entry = self.entry(key).filter(|entry| entry.state == StandardKeyState::Released)?;
+        Some(self.now.duration_since(entry.released_at?))
+    }
+
+    fn all_pressed_keys(&self) -> Vec<StandardKey> {
+        self.pressed_keys_iter().copied().collect()
+    }
+
+    fn current_state_snapshot(&self) -> Vec<(StandardKey, StandardKeyState)> {
+        self.key_states_iter().map(|(key, state)| (*key, state)).collect()
+    }
+
+    fn pressed_keys_iter(&self) -> impl Iterator<Item = &StandardKey> + '_ {
+        self.keys.iter().filter(|entry| entry.state == StandardKeyState::Pressed).map(|entry| &entry.key)
+    }
+
+    fn key_states_iter(&self) -> impl Iterator<Item = (&StandardKey, StandardKeyState)> + '_ {
+        self.keys.iter().map(|entry| (&entry.key, entry.state))
+    }
+}
+
+impl ProcessingLayerControl for TestProcessing {
+    type Event = StandardKeyEvent;
+
+    fn reset(&mut self) {
+        *self = Self {
+            suppress_held_on_focus: self.suppress_held_on_focus,
+            repeat_mode: self.repeat_mode,
+            ..Self::default()
+        };
+    }
+
+    fn update(&mut self, events: &[StandardKeyEvent]) {
+        self.frame_events.extend_from_slice(events);
+        for event in events {
+            self.apply(event.key, event.state, event.value, event.repeat, event.timestamp, InputOrigin::Device);
+        }
+    }
+
+    fn inject_synthetic(&mut self, events: &[SyntheticKeyEvent<StandardKey>]) {
+        for event in events {
+            let state = if event.pressed { StandardKeyState::Pressed } else { StandardKeyState::Released };
+            self.apply(event.key, state, None, false, event.timestamp, InputOrigin::Synthetic);
+        }
+    }
+
+    fn inject_synthetic_axis(&mut self, events: &[SyntheticAxisEvent<StandardKey>]) {
+        for event in events {
+            self.apply(event.key, StandardKeyState::Changed, Some(event.value), false, event.timestamp, InputOrigin::Synthetic);
+        }
+    }
+
+    fn handle_signal(&mut self, signal: RawSignal) {
+        match signal {
+            RawSignal::FocusLost | RawSignal::Suspended => {
+                for entry in self.keys.iter_mut().filter(|entry| entry.state == StandardKeyState::Pressed) {
+                    entry.state = StandardKeyState::Released;
+                    entry.value = 0.0;
+                    entry.just_released = true;
+                    entry.released_at = Some(self.now);
+                }
+                self.focused = false;
+                self.reconciling = true;
+                self.regained = false;
+            }
+            RawSignal::FocusGained | RawSignal::Resumed => {
+                self.focused = true;
+                self.regained = true;
+            }
+            RawSignal::LayoutChanged => {}
+        }
+    }
+
+    fn update_text(&mut self, events: &[TextInputEvent]) {
+        for event in events {
+            match event {
+                TextInputEvent::Commit(text) => self.frame_text.push_str(text),
+                TextInputEvent::Preedit { text, cursor } => self.preedit = Some((text.clone(), cursor.clone())),
+                TextInputEvent::PreeditCleared => self.preedit = None,
+            }
+        }
+    }
+
+    fn set_suppress_held_on_focus(&mut self, suppress: bool) {
+        self.suppress_held_on_focus = suppress;
+    }
+
+    fn set_repeat_mode(&mut self, mode: KeyRepeatMode) {
+        self.repeat_mode = mode;
+    }
+
+    fn begin_frame(&mut self) {
+        self.clear_transitions();
+        for entry in self.keys.iter_mut().filter(|entry| is_relative(entry.key)) {
+            entry.value = 0.0;
+        }
+        self.frame_events.clear();
+        self.frame_text.clear();
+        self.frame_start = self.now;
+    }
+
+    fn end_frame(&mut self) {
+        if let KeyRepeatMode::Software(config) = self.repeat_mode {
+            for entry in self.keys.iter_mut().filter(|entry| entry.state == StandardKeyState::Pressed) {
+                let Some(pressed_at) = entry.pressed_at else {
+                    continue;
+                };
+                let held_before = self.frame_start.checked_duration_since(pressed_at).unwrap_or_default();
+                entry.repeats += config.repeats_between(held_before, self.now.duration_since(pressed_at));
+            }
+        }
+        if self.focused && self.reconciling {
+            if self.regained {
+                self.regained = false;
+            } else {
+                self.reconciling = false;
+            }
+        }
+    }
+
+    fn clear_transitions(&mut self) {
+        for entry in &mut self.keys {
+            entry.just_pressed = false;
+            entry.just_released = false;
+            entry.repeats = 0;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.reset();
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Historial
+// -----------------------------------------------------------------------------
+
+/// Historial con capacidad fija: al llenarse descarta el evento más antiguo.
+#[derive(Debug, Clone)]
+pub struct TestHistory {
+    events: Vec<(StandardKey, StandardKeyState, Duration)>,
+    /// Frame de cada evento de `events`.
+    frames: Vec<u64>,
+    frame: u64,
+    now: Duration,
+}
+
+impl Default for TestHistory {
+    fn default() -> Self {
+        Self {
+            events: Vec::with_capacity(Self::CAPACITY),
+            frames: Vec::with_capacity(Self::CAPACITY),
+            frame: 0,
+            now: Duration::ZERO,
+        }
+    }
+}
+
+impl TestHistory {
+    /// Número máximo de eventos conservados.
+    pub const CAPACITY: usize = 256;
+
+    fn since_frame(&self, prev_frames: usize) -> u64 {
+        self.frame.saturating_sub(prev_frames as u64)
+    }
+
+    fn pressed_since(&self, key: &StandardKey, frame: u64, time: Duration) -> bool {
+        self.events
+            .iter()
+            .zip(&self.frames)
+            .any(|((k, state, at), f)| k == key && *state == StandardKeyState::Pressed && *f >= frame && *at >= time)
+    }
+
+    fn presses(&self) -> impl DoubleEndedIterator<Item = (StandardKey, Duration)> + '_ {
+        self.events.iter().filter(|(_, state, _)| *state == StandardKeyState::Pressed).map(|(key, _, at)| (*key, *at))
+    }
+}
+
+/// Busca `sequence` en `presses`, con como mucho `max_interval` entre
+/// pulsaciones consecutivas de la secuencia.
+fn match_sequence<'a>(
+    presses: impl Iterator<Item = (StandardKey, Duration)>,
+    mut sequence: impl Iterator<Item = &'a StandardKey> + Clone,
+    max_interval: Duration,
+) -> bool {
+    let Some(first) = sequence.clone().next() else {
+        return true;
+    };
+    let mut remaining = sequence.clone();
+    let mut last = None;
+    for (key, at) in presses {
+        if last.is_some_and(|last: Duration| at.abs_diff(last) > max_interval) {
+            remaining = sequence.clone();
+            last = None;
+        }
+        let mut next = remaining.clone();
+        if next.next() == Some(&key) {
+            remaining = next;
+            last = Some(at);
+        } else if key == *first {
+            remaining = sequence.clone();
+            remaining.next();
+            last = Some(at);
+        } else {
+            continue;
+        }
+        if remaining.clone().next().is_none() {
+            return true;
+        }
+    }
+    sequence.next().is_none()
+}
+
+impl HistoryStateExt for TestHistory {
+    type KeyCode = StandardKey;
+    type KeyState = StandardKeyState;
+
+    fn is_empty(&self) -> bool { self.events.is_empty() }
+    fn len(&self) -> usize { self.events.len() }
+    fn match_combo(&self, combo: &[Self::KeyCode]) -> bool {
+        combo.iter().all(|key| {
+            self.events.iter().rev().find(|(k, _, _)| k == key).is_some_and(|(_, state, _)| *state == StandardKeyState::Pressed)
+        })
+    }
+    fn match_combo_in_frames(&self, combo: &[Self::KeyCode], prev_frames: usize) -> bool {
+        let since = self.since_frame(prev_frames);
+        combo.iter().all(|key| self.pressed_since(key, since, Duration::ZERO))
+    }
+    fn match_key_in_frames(&self, key: &Self::KeyCode, state: &Self::KeyState, prev_frames: usize) -> bool {
+        let since = self.since_frame(prev_frames);
+        self.events.iter().zip(&self.frames).any(|((k, s, _), f)| k == key && s == state && *f >= since)
+    }
+    fn match_combo_in_time_window(&self, combo: &[Self::KeyCode], max_window: Duration) -> bool {
+        let since = self.now.saturating_sub(max_window);
+        combo.iter().all(|key| self.pressed_since(key, 0, since))
+    }
+    fn match_ordered_sequence(&self, sequence: &[Self::KeyCode], max_interval: Duration) -> bool {
+        match_sequence(self.presses(), sequence.iter(), max_interval)
+    }
+    fn match_recent_ordered_sequence(&self, sequence: &[Self::KeyCode], max_interval: Duration) -> bool {
+        match_sequence(self.presses().rev(), sequence.iter().rev(), max_interval)
+    }
+    fn current_time(&self) -> Duration { self.now }
+    fn last_event_time(&self, key: &Self::KeyCode, state: &Self::KeyState) -> Option<Duration> {
+        self.events.iter().rev().find(|(k, s, _)| k == key && s == state).map(|(_, _, at)| *at)
+    }
+    fn history(&self) -> &Vec<(Self::KeyCode, Self::KeyState, Duration)> { &self.events }
+}
+
+impl HistoryControlExt for TestHistory {
+    type KeyCode = StandardKey;
+    type KeyState = StandardKeyState;
+
+    fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    fn add_event(&mut self, key: impl Into<Self::KeyCode>, state: impl Into<Self::KeyState>, timestamp: Duration) {
+        if self.events.len() == Self::CAPACITY {
+            self.events.remove(0);
+            self.frames.remove(0);
+        }
+        self.events.push((key.into(), state.into(), timestamp));
+        self.frames.push(self.frame);
+        self.now = self.now.max(timestamp);
+    }
+
+    fn end_frame(&mut self) {}
+
+    fn clear(&mut self) {
+        self.events.clear();
+        self.frames.clear();
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Macros y suscripciones
+// -----------------------------------------------------------------------------
+
+/// Motor de macros que nunca inicia macros ni turbo; solo guarda la política.
+#[derive(Debug, Clone, Default)]
+pub struct TestMacros {
+    policy: MacroPolicy,
+}
+
+impl MacroEngineExt for TestMacros {
+    type KeyCode = StandardKey;
+
+    fn tick<M, P>(&mut self, _mapping: &M, _processing: &P, _now: Timestamp) -> Vec<SyntheticKeyEvent<Self::KeyCode>>
+    where
+        M: MappingLayerState<KeyCode = Self::KeyCode>,
+        P: ProcessingLayerState<KeyCode = Self::KeyCode>,
+    {
+        Vec::new()
+    }
+
+    fn tick_into<M, P>(&mut self, _mapping: &M, _processing: &P, _now: Timestamp, _out: &mut Vec<SyntheticKeyEvent<Self::KeyCode>>)
+    where
+        M: MappingLayerState<KeyCode = Self::KeyCode>,
+        P: ProcessingLayerState<KeyCode = Self::KeyCode>,
+    {
+    }
+
+    fn start<M>(&mut self, _mapping: &M, _name: &str, _now: Timestamp) -> bool
+    where
+        M: MappingLayerState<KeyCode = Self::KeyCode>,
+    {
+        false
+    }
+
+    fn cancel(&mut self, _name: &str) -> bool { false }
+    fn is_running(&self, _name: &str) -> bool { false }
+    fn policy(&self) -> MacroPolicy { self.policy }
+    fn set_policy(&mut self, policy: MacroPolicy) { self.policy = policy; }
+    fn reset(&mut self) {}
+}
+
+/// Qué notificaciones recibe una suscripción.
+#[derive(Debug, Clone)]
+enum Filter {
+    Kind(NotificationKind),
+    Action(String, Option<ActionPhase>),
+}
+
+impl Filter {
+    fn matches(&self, notification: &TestNotification) -> bool {
+        match (self, notification) {
+            (Filter::Kind(kind), notification) => notification.kind() == *kind,
+            (Filter::Action(action, phase), InputNotification::Action(event)) => {
+                *event.action == **action && phase.is_none_or(|phase| phase == event.phase)
+            }
+            (Filter::Action(..), _) => false,
+        }
+    }
+}
+
+/// Registro de suscripciones que anota las entregas en lugar de ejecutar
+/// callbacks.
+///
+/// Los callbacks se descartan al suscribirse: los runtimes de Bevy deben
+/// ser `Send + Sync` y el contrato no exige callbacks `Send`. Cada
+/// `dispatch` deja en [`TestSubscriptions::delivered`] las entregas que
+/// habría hecho, en orden.
+#[derive(Debug, Clone, Default)]
+pub struct TestSubscriptions {
+    next: u64,
+    subscribers: Vec<(SubscriptionHandle, Filter)>,
+    queue: Vec<TestNotification>,
+    delivered: Vec<(SubscriptionHandle, TestNotification)>,
+}
+
+impl TestSubscriptions {
+    /// Entregas del último `dispatch`: suscripción y notificación.
+    pub fn delivered(&self) -> &[(SubscriptionHandle, TestNotification)] {
+        &self.delivered
+    }
+
+    fn add(&mut self, filter: Filter) -> SubscriptionHandle {
+        let handle = SubscriptionHandle::from_raw(self.next);
+        self.next += 1;
+        self.subscribers.push((handle, filter));
+        handle
+    }
+}
+
+impl InputSubscriptionExt for TestSubscriptions {
+    type Ctx = &'static str;
+    type KeyCode = StandardKey;
+    type DeviceKind = StandardDevice;
+
+    fn subscribe(&mut self, kind: NotificationKind, _callback: impl FnMut(&TestNotification) + 'static) -> SubscriptionHandle {
+        self.add(Filter::Kind(kind))
+    }
+
+    fn subscribe_action(&mut self, action: &str, phase: Option<ActionPhase>, _callback: impl FnMut(&TestNotification) + 'static) -> SubscriptionHandle {
+        self.add(Filter::Action(String::from(action), phase))
+    }
+
+    fn unsubscribe(&mut self, handle: SubscriptionHandle) -> bool {
+        let before = self.subscribers.len();
+        self.subscribers.retain(|(h, _)| *h != handle);
+        self.subscribers.len() != before
+    }
+
+    fn is_subscribed(&self, handle: SubscriptionHandle) -> bool {
+        self.subscribers.iter().any(|(h, _)| *h == handle)
+    }
+
+    fn enqueue(&mut self, notification: TestNotification) {
+        self.queue.push(notification);
+    }
+
+    fn dispatch(&mut self) {
+        self.delivered.clear();
+        for notification in self.queue.drain(..) {
+            for (handle, filter) in &self.subscribers {
+                if filter.matches(&notification) {
+                    self.delivered.push((*handle, notification.clone()));
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.subscribers.clear();
+        self.queue.clear();
+        self.delivered.clear();
+    }
+}
+
+
+// -----------------------------------------------------------------------------
+// Capa de juego y runtime
+// -----------------------------------------------------------------------------
+
+/// `GameContract` sobre las capas de test.
+///
+/// Las acciones se evalúan con los bindings del contexto activo y las
+/// inyecciones de `inject_action`; las ventanas temporales usan
+/// `TestHistory`.
+#[derive(Debug, Clone, Default)]
+pub struct TestGame {
+    pub mapping: TestMapping,
+    pub processing: TestProcessing,
+    pub history: TestHistory,
+}
+
+impl TestGame {
+    fn keys<'a>(&'a self, action: &'a str) -> impl Iterator<Item = &'a StandardKey> + 'a {
+        let section = self.mapping.current_section();
+        section.keys(action).filter(move |_| section.enabled)
+    }
+
+    /// Instante de la última transición `state` de la acción, si ocurrió
+    /// dentro de `window`.
+    fn within(&self, action: &str, state: StandardKeyState, window: Duration) -> Option<Duration> {
+        let at = self.keys(action).filter_map(|key| self.history.last_event_time(key, &state)).max()?;
+        (self.history.current_time().saturating_sub(at) <= window).then_some(at)
+    }
+
+    fn count(&self, action: &str, phase: ActionPhase) -> usize {
+        self.mapping.action_events().iter().filter(|event| *event.action == *action && event.phase == phase).count()
+    }
+}
+
+impl GameContract for TestGame {
+    type MappingLayer = TestMapping;
+    type ProcessingLayer = TestProcessing;
+    type HistoryLayer = TestHistory;
+
+    fn action_pressed(&self, action: &str) -> bool { self.count(action, ActionPhase::Performed) > 0 }
+    fn action_released(&self, action: &str) -> bool { self.count(action, ActionPhase::Canceled) > 0 }
+    fn action_held(&self, action: &str) -> bool {
+        self.mapping.is_injected(action) || self.keys(action).any(|key| self.processing.is_pressed(key))
+    }
+    fn action_value(&self, action: &str) -> f32 {
+        if self.mapping.is_injected(action) {
+            return 1.0;
+        }
+        self.keys(action)
+            .map(|key| self.processing.axis_value(key))
+            .fold(0.0, |strongest, value| if value.abs() > f32::abs(strongest) { value } else { strongest })
+    }
+    fn action_duration(&self, action: &str) -> f32 {
+        self.keys(action).filter_map(|key| self.processing.pressed_duration(key)).max().map_or(0.0, |held| held.as_secs_f32())
+    }
+    fn action_events(&self) -> &[ActionEvent<StandardDevice>] { self.mapping.action_events() }
+    fn action_press_count(&self, action: &str) -> usize { self.count(action, ActionPhase::Performed) }
+    fn action_is_synthetic(&self, action: &str) -> bool {
+        self.mapping.is_injected(action) || self.keys(action).any(|key| self.processing.key_origin(key) == Some(InputOrigin::Synthetic))
+    }
+    fn is_buffered(&self, action: &str) -> bool {
+        self.mapping
+            .buffer_window(action)
+            .and_then(|window| self.within(action, StandardKeyState::Pressed, window))
+            .is_some_and(|at| !self.mapping.is_consumed(action, 0, at))
+    }
+    fn consume_buffered(&self, action: &str) -> bool {
+        self.mapping
+            .buffer_window(action)
+            .and_then(|window| self.within(action, StandardKeyState::Pressed, window))
+            .is_some_and(|at| self.mapping.consume(action, 0, at))
+    }
+    fn action_pressed_within(&self, action: &str, window: Duration) -> bool { self.within(action, StandardKeyState::Pressed, window).is_some() }
+    fn action_released_within(&self, action: &str, window: Duration) -> bool { self.within(action, StandardKeyState::Released, window).is_some() }
+    fn consume_pressed_within(&self, action: &str, window: Duration) -> bool {
+        self.within(action, StandardKeyState::Pressed, window).is_some_and(|at| self.mapping.consume(action, 0, at))
+    }
+    fn consume_released_within(&self, action: &str, window: Duration) -> bool {
+        self.within(action, StandardKeyState::Released, window).is_some_and(|at| self.mapping.consume(action, 1, at))
+    }
+    fn mapping_layer(&self) -> &TestMapping { &self.mapping }
+    fn processing_layer(&self) -> &TestProcessing { &self.processing }
+    fn history_layer(&self) -> &TestHistory { &self.history }
+}
+
+/// Runtime completo sobre las capas de test y [`StandardInputFilter`].
+///
+/// Sigue el orden de `RuntimeInputExt::end_frame`: filtro, procesamiento
+/// (eventos reales, sintéticos y texto), historial, resolución de acciones,
+/// macros y despacho de notificaciones. Además anota las señales y cambios
+/// de dispositivo recibidos.
+#[derive(Debug, Clone)]
+pub struct TestRuntime {
+    game: TestGame,
+    filter: StandardInputFilter<StandardKeyEvent>,
+    macros: TestMacros,
+    subscriptions: TestSubscriptions,
+    raw: Vec<StandardKeyEvent>,
+    filtered: Vec<StandardKeyEvent>,
+    text: Vec<TextInputEvent>,
+    /// Eventos sintéticos del frame (inyección y macros del frame anterior).
+    synthetic: Vec<SyntheticKeyEvent<StandardKey>>,
+    /// Teclas pulsadas por `inject_action` y frames que les quedan.
+    injected_keys: Vec<(StandardKey, u32)>,
+    now: Timestamp,
+    signals: Vec<RawSignal>,
+    device_changes: Vec<(StandardDevice, DeviceChange)>,
+}
+
+impl TestRuntime {
+    /// Crea el runtime con la capa de mapeo indicada y sin filtros.
+    pub fn new(mapping: TestMapping) -> Self {
+        Self {
+            game: TestGame { mapping, ..Default::default() },
+            filter: StandardInputFilter::default(),
+            macros: TestMacros::default(),
+            subscriptions: TestSubscriptions::default(),
+            raw: Vec::new(),
+            filtered: Vec::new(),
+            text: Vec::new(),
+            synthetic: Vec::new(),
+            injected_keys: Vec::new(),
+            now: Timestamp::ZERO,
+            signals: Vec::new(),
+            device_changes: Vec::new(),
+        }
+    }
+
+    /// Aplica la configuración de filtros de accesibilidad.
+    pub fn with_filters(mut self, config: AccessibilityFilters) -> Self {
+        self.filter.set_config(config);
+        self
+    }
+
+    /// Señales recibidas con `push_signal`, en orden.
+    pub fn signals(&self) -> &[RawSignal] {
+        &self.signals
+    }
+
+    /// Cambios de dispositivo recibidos con `push_device_change`, en orden.
+    pub fn device_changes(&self) -> &[(StandardDevice, DeviceChange)] {
+        &self.device_changes
+    }
+}
+
+impl RuntimeInputExt for TestRuntime {
+    type Event = StandardKeyEvent;
+    type GameLayer = TestGame;
+    type HistoryControl = TestHistory;
+    type ProcessingControl = TestProcessing;
+    type MappingControl = TestMapping;
+    type InputFilter = StandardInputFilter<StandardKeyEvent>;
+    type MacroEngine = TestMacros;
+    type Subscriptions = TestSubscriptions;
+
+    fn begin_frame(&mut self) {
+        self.raw.clear();
+        self.text.clear();
+        self.game.history.begin_frame();
+        self.game.processing.begin_frame();
+        self.game.mapping.begin_frame();
+    }
+
+    fn push_raw_event(&mut self, event: StandardKeyEvent) {
+        self.now = self.now.max(event.timestamp);
+        self.raw.push(event);
+    }
+
+    fn push_signal(&mut self, signal: RawSignal) {
+        self.signals.push(signal);
+        self.game.processing.handle_signal(signal);
+        if matches!(signal, RawSignal::FocusLost | RawSignal::Suspended) {
+            self.filter.reset();
+        }
+    }
+
+    fn push_text_event(&mut self, event: TextInputEvent) {
+        self.text.push(event);
+    }
+
+    fn inject_key(&mut self, key: StandardKey, pressed: bool) {
+        self.synthetic.push(SyntheticKeyEvent { key, pressed, timestamp: self.now });
+    }
+
+    fn inject_axis(&mut self, key: StandardKey, value: f32) {
+        self.game.processing.inject_synthetic_axis(&[SyntheticAxisEvent { key, value, timestamp: self.now }]);
+    }
+
+    fn inject_action(&mut self, action: &str, frames: u32, mode: ActionInjection) -> bool {
+        match mode {
+            ActionInjection::BypassMapping => self.game.mapping.inject_action(action, frames),
+            ActionInjection::ThroughMapping => {
+                let Some(key) = self.game.mapping.key_for_action(action).filter(|_| frames > 0) else {
+                    return false;
+                };
+                self.inject_key(key, true);
+                self.injected_keys.push((key, frames));
+                true
+            }
+        }
+    }
+
+    fn push_device_change(&mut self, device: StandardDevice, change: DeviceChange) {
+        self.device_changes.push((device, change));
+        self.subscriptions.enqueue(InputNotification::Device { device, change });
+    }
+
+    fn end_frame(&mut self) {
+        let game = &mut self.game;
+        self.filtered.clear();
+        self.filter.filter_into(&self.raw, self.now, &mut self.filtered);
+        game.processing.update(&self.filtered);
+        game.processing.inject_synthetic(&self.synthetic);
+        self.synthetic.clear();
+        game.processing.update_text(&self.text);
+        for event in &self.filtered {
+            game.history.add_event(event.key, event.state, event.timestamp.as_duration());
+        }
+        game.history.end_frame();
+        game.processing.end_frame();
+        game.mapping.resolve_actions(&game.processing, &game.history);
+
+        self.macros.tick_into(&game.mapping, &game.processing, self.now, &mut self.synthetic);
+        let (synthetic, now) = (&mut self.synthetic, self.now);
+        self.injected_keys.retain_mut(|(key, frames)| {
+            *frames -= 1;
+            if *frames == 0 {
+                synthetic.push(SyntheticKeyEvent { key: *key, pressed: false, timestamp: now });
+            }
+            *frames > 0
+        });
+
+        for event in game.mapping.action_events() {
+            self.subscriptions.enqueue(InputNotification::Action(event.clone()));
+        }
+        game.mapping.end_frame();
+        self.subscriptions.dispatch();
+    }
+
+    fn drain_action_events(&mut self) -> Vec<ActionEvent<StandardDevice>> {
+        self.game.mapping.drain_action_events()
+    }
+
+    fn drain_action_events_into(&mut self, out: &mut Vec<ActionEvent<StandardDevice>>) {
+        self.game.mapping.drain_action_events_into(out);
+    }
+
+    fn history_mut(&mut self) -> &mut TestHistory {
+        &mut self.game.history
+    }
+
+    fn processing_mut(&mut self) -> &mut TestProcessing {
+        &mut self.game.processing
+    }
+
+    fn mapping_mut(&mut self) -> &mut TestMapping {
+        &mut self.game.mapping
+    }
+
+    fn input_filter_mut(&mut self) -> &mut StandardInputFilter<StandardKeyEvent> {
+        &mut self.filter
+    }
+
+    fn macro_engine_mut(&mut self) -> &mut TestMacros {
+        &mut self.macros
+    }
+
+    fn subscriptions_mut(&mut self) -> &mut TestSubscriptions {
+        &mut self.subscriptions
+    }
+
+    fn game_layer(&self) -> &TestGame {
+        &self.game
+    }
+}